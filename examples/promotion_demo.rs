@@ -14,7 +14,7 @@ fn main() {
     
     // Demo 1: Default promotion (Queen)
     let mut board1 = Board::from_fen(fen).unwrap();
-    board1.apply_move(Coordinates::new(1, 7), Coordinates::new(1, 8)).unwrap();
+    board1.apply_move_coords(Coordinates::new(1, 7), Coordinates::new(1, 8)).unwrap();
     println!("1. Default promotion (Queen): {:?}", 
              board1.get(Coordinates::new(1, 8)).unwrap().piece_type);
     
@@ -0,0 +1,269 @@
+use crate::chess::board::board::Piece;
+use crate::chess::piece::piece::{Color, PieceType};
+
+/// Piece types in bitboard index order
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+/// Squares of the same color as a1
+pub const DARK_SQUARES: u64 = 0xAA55_AA55_AA55_AA55;
+
+/// Ray directions as (file, rank) steps; the first four move towards higher square indices
+const DIRECTIONS: [(i8, i8); 8] = [
+    (1, 0),
+    (0, 1),
+    (1, 1),
+    (-1, 1),
+    (-1, 0),
+    (0, -1),
+    (-1, -1),
+    (1, -1),
+];
+
+/// Indices into `DIRECTIONS` of the diagonal rays
+const BISHOP_DIRECTIONS: [usize; 4] = [2, 3, 6, 7];
+
+/// Indices into `DIRECTIONS` of the orthogonal rays
+const ROOK_DIRECTIONS: [usize; 4] = [0, 1, 4, 5];
+
+static KNIGHT_ATTACKS: [u64; 64] = leaper_table(&[
+    (2, 1),
+    (1, 2),
+    (-1, 2),
+    (-2, 1),
+    (-2, -1),
+    (-1, -2),
+    (1, -2),
+    (2, -1),
+]);
+
+static KING_ATTACKS: [u64; 64] = leaper_table(&[
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+]);
+
+/// Pawn capture targets, indexed by `[color][square]` with White first
+static PAWN_ATTACKS: [[u64; 64]; 2] = [
+    leaper_table(&[(-1, 1), (1, 1)]),
+    leaper_table(&[(-1, -1), (1, -1)]),
+];
+
+/// Squares from a square to the board edge, excluding the square itself, per direction
+static RAYS: [[u64; 64]; 8] = ray_tables();
+
+/// Builds the attack table of a piece that jumps by fixed offsets
+const fn leaper_table<const N: usize>(offsets: &[(i8, i8); N]) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let x = (square % 8) as i8;
+        let y = (square / 8) as i8;
+        let mut i = 0;
+        while i < N {
+            let nx = x + offsets[i].0;
+            let ny = y + offsets[i].1;
+            if nx >= 0 && nx < 8 && ny >= 0 && ny < 8 {
+                table[square] |= 1 << (ny * 8 + nx);
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+    table
+}
+
+/// Builds the ray tables for every direction
+const fn ray_tables() -> [[u64; 64]; 8] {
+    let mut rays = [[0; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let (dx, dy) = DIRECTIONS[direction];
+        let mut square = 0;
+        while square < 64 {
+            let mut x = (square % 8) as i8 + dx;
+            let mut y = (square / 8) as i8 + dy;
+            while x >= 0 && x < 8 && y >= 0 && y < 8 {
+                rays[direction][square] |= 1 << (y * 8 + x);
+                x += dx;
+                y += dy;
+            }
+            square += 1;
+        }
+        direction += 1;
+    }
+    rays
+}
+
+/// Gets the bitboard index of a piece type
+fn piece_index(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    }
+}
+
+/// Gets the bitboard index of a color
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+/// Gets the bit of a square index (`8 * (rank - 1) + (file - 1)`, so a1 = 0 and h8 = 63)
+pub fn square_bit(square: usize) -> u64 {
+    1 << square
+}
+
+/// Iterates over the square indices of the set bits, from a1 towards h8
+pub fn squares(mut bitboard: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if bitboard == 0 {
+            return None;
+        }
+        let square = bitboard.trailing_zeros() as usize;
+        bitboard &= bitboard - 1;
+        Some(square)
+    })
+}
+
+/// Squares attacked by a knight on `square`
+pub fn knight_attacks(square: usize) -> u64 {
+    KNIGHT_ATTACKS[square]
+}
+
+/// Squares attacked by a king on `square`
+pub fn king_attacks(square: usize) -> u64 {
+    KING_ATTACKS[square]
+}
+
+/// Squares attacked by a pawn of `color` on `square`
+pub fn pawn_attacks(color: Color, square: usize) -> u64 {
+    PAWN_ATTACKS[color_index(color)][square]
+}
+
+/// Squares attacked along one ray, stopping at and including the first occupied square
+fn ray_attacks(square: usize, occupied: u64, direction: usize) -> u64 {
+    let ray = RAYS[direction][square];
+    let blockers = ray & occupied;
+    if blockers == 0 {
+        return ray;
+    }
+    let blocker = if direction < 4 {
+        blockers.trailing_zeros()
+    } else {
+        63 - blockers.leading_zeros()
+    };
+    ray ^ RAYS[direction][blocker as usize]
+}
+
+/// Squares attacked by a bishop on `square` given the occupied squares
+pub fn bishop_attacks(square: usize, occupied: u64) -> u64 {
+    BISHOP_DIRECTIONS
+        .iter()
+        .fold(0, |attacks, &d| attacks | ray_attacks(square, occupied, d))
+}
+
+/// Squares attacked by a rook on `square` given the occupied squares
+pub fn rook_attacks(square: usize, occupied: u64) -> u64 {
+    ROOK_DIRECTIONS
+        .iter()
+        .fold(0, |attacks, &d| attacks | ray_attacks(square, occupied, d))
+}
+
+/// Squares attacked by a queen on `square` given the occupied squares
+pub fn queen_attacks(square: usize, occupied: u64) -> u64 {
+    bishop_attacks(square, occupied) | rook_attacks(square, occupied)
+}
+
+/// Squares strictly between two squares on a shared rank, file or diagonal, or none if they are not aligned
+pub fn between(a: usize, b: usize) -> u64 {
+    RAYS.iter()
+        .find(|rays| rays[a] & square_bit(b) != 0)
+        .map_or(0, |rays| (rays[a] ^ rays[b]) & !square_bit(b))
+}
+
+/// Per-piece-type and per-color square sets of a position
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bitboards {
+    pieces: [u64; 6],
+    colors: [u64; 2],
+}
+
+impl Bitboards {
+    /// Gets the squares holding pieces of a type, of either color
+    pub fn pieces(&self, piece_type: PieceType) -> u64 {
+        self.pieces[piece_index(piece_type)]
+    }
+
+    /// Gets the squares holding pieces of a color
+    pub fn color(&self, color: Color) -> u64 {
+        self.colors[color_index(color)]
+    }
+
+    /// Gets the squares holding pieces of a type and color
+    pub fn pieces_of(&self, color: Color, piece_type: PieceType) -> u64 {
+        self.pieces(piece_type) & self.color(color)
+    }
+
+    /// Gets every occupied square
+    pub fn occupied(&self) -> u64 {
+        self.colors[0] | self.colors[1]
+    }
+
+    /// Gets the color and type of the piece on a square
+    pub fn piece_at(&self, square: usize) -> Option<(Color, PieceType)> {
+        let bit = square_bit(square);
+        let color = if self.colors[0] & bit != 0 {
+            Color::White
+        } else if self.colors[1] & bit != 0 {
+            Color::Black
+        } else {
+            return None;
+        };
+        let piece_type = PIECE_TYPES
+            .into_iter()
+            .find(|&piece_type| self.pieces(piece_type) & bit != 0)?;
+        Some((color, piece_type))
+    }
+
+    /// Replaces the contents of a square
+    pub(crate) fn set(&mut self, square: usize, piece: Option<Piece>) {
+        let bit = square_bit(square);
+        for set in self.pieces.iter_mut().chain(self.colors.iter_mut()) {
+            *set &= !bit;
+        }
+        if let Some(piece) = piece {
+            self.pieces[piece_index(piece.piece_type)] |= bit;
+            self.colors[color_index(piece.color)] |= bit;
+        }
+    }
+
+    /// Gets the pieces of `color` attacking `square`, with sliding attacks blocked by `occupied`
+    pub fn attackers_to(&self, square: usize, color: Color, occupied: u64) -> u64 {
+        let diagonal = self.pieces(PieceType::Bishop) | self.pieces(PieceType::Queen);
+        let orthogonal = self.pieces(PieceType::Rook) | self.pieces(PieceType::Queen);
+        let attackers = (knight_attacks(square) & self.pieces(PieceType::Knight))
+            | (king_attacks(square) & self.pieces(PieceType::King))
+            | (pawn_attacks(color.reverse(), square) & self.pieces(PieceType::Pawn))
+            | (bishop_attacks(square, occupied) & diagonal)
+            | (rook_attacks(square, occupied) & orthogonal);
+        attackers & self.color(color)
+    }
+}
@@ -1,6 +1,10 @@
 impl Board {
-    /// Executes a move with full rule validation
-    pub fn apply_move(&mut self, from: Coordinates, to: Coordinates) -> Result<(), &'static str> {
+    /// Executes a move given as a from/to pair, promoting to a Queen
+    pub fn apply_move_coords(
+        &mut self,
+        from: Coordinates,
+        to: Coordinates,
+    ) -> Result<(), ChessError> {
         self.apply_move_with_promotion(from, to, None)
     }
 
@@ -10,143 +14,194 @@ impl Board {
         from: Coordinates,
         to: Coordinates,
         promotion: Option<PieceType>,
-    ) -> Result<(), &'static str> {
-        // Validate move legality first
-        let legal_moves = self.generate_legal_moves();
-        if !legal_moves.contains(&(from, to)) {
-            return Err("Illegal move");
-        }
+    ) -> Result<(), ChessError> {
+        let mv = self.find_legal_move(from, to, promotion)?;
+        self.execute_move(&mv);
+        Ok(())
+    }
 
-        // If this is a promotion move, validate the promotion piece
-        if let Some(piece) = self.get(from) {
-            if piece.piece_type == PieceType::Pawn {
-                let promotion_rank = if piece.color == Color::White { 8 } else { 1 };
-                if to.y == promotion_rank {
-                    if let Some(promo_piece) = promotion {
-                        match promo_piece {
-                            PieceType::Queen
-                            | PieceType::Rook
-                            | PieceType::Bishop
-                            | PieceType::Knight => {}
-                            _ => return Err("Invalid promotion piece"),
-                        }
-                    }
-                }
-            }
+    /// Executes a `Move` with full rule validation
+    pub fn apply_move(&mut self, mv: &Move) -> Result<(), ChessError> {
+        self.make_move(mv).map(|_| ())
+    }
+
+    /// Executes a `Move` with full rule validation and returns the record needed to take it back
+    pub fn make_move(&mut self, mv: &Move) -> Result<Undo, ChessError> {
+        let mv = self.resolve_move(mv)?;
+        Ok(self.execute_move(&mv))
+    }
+
+    /// Resolves a `Move` to the fully flagged legal move it names, keeping castling apart from
+    /// plain king moves
+    pub(crate) fn resolve_move(&self, mv: &Move) -> Result<Move, ChessError> {
+        if mv.is_castle() {
+            // In Chess960 a castling move can share its squares with a plain king move
+            self.generate_moves()
+                .into_iter()
+                .find(|legal| legal.from == mv.from && legal.kind == mv.kind)
+                .ok_or_else(|| ChessError::IllegalMove(mv.to_uci()))
+        } else {
+            self.find_legal_move(mv.from, mv.to, mv.promotion)
         }
+    }
 
-        // Handle castling
-        if let Some(piece) = self.get(from) {
-            if piece.piece_type == PieceType::King {
-                let rank = from.y;
-                // Kingside castling
-                if from.x == 5 && to.x == 7 {
-                    self.set(Coordinates { x: 8, y: rank }, None);
-                    self.set(
-                        Coordinates { x: 6, y: rank },
-                        Some(Piece {
-                            piece_type: PieceType::Rook,
-                            color: piece.color,
-                            has_moved: true,
-                        }),
-                    );
-                }
-                // Queenside castling
-                if from.x == 5 && to.x == 3 {
-                    self.set(Coordinates { x: 1, y: rank }, None);
-                    self.set(
-                        Coordinates { x: 4, y: rank },
-                        Some(Piece {
-                            piece_type: PieceType::Rook,
-                            color: piece.color,
-                            has_moved: true,
-                        }),
-                    );
-                }
-                // Update castling rights
-                match piece.color {
-                    Color::White => {
-                        self.castling_rights[0] = false;
-                        self.castling_rights[1] = false;
-                    }
-                    Color::Black => {
-                        self.castling_rights[2] = false;
-                        self.castling_rights[3] = false;
-                    }
-                }
+    /// Takes back a move made with `make_move`, restoring the exact previous position
+    pub fn unmake_move(&mut self, undo: Undo) {
+        let mv = undo.mv;
+        let rook_squares = castling_rook_squares(&undo.castling_rights, &mv);
+        self.set(mv.to, None);
+        match mv.kind {
+            MoveKind::EnPassant => {
+                self.set(Coordinates::new(mv.to.x, mv.from.y), undo.captured);
             }
-            // Update castling rights when rook moves
-            if piece.piece_type == PieceType::Rook {
-                match (piece.color, from.x, from.y) {
-                    (Color::White, 1, 1) => self.castling_rights[1] = false, // White queenside
-                    (Color::White, 8, 1) => self.castling_rights[0] = false, // White kingside
-                    (Color::Black, 1, 8) => self.castling_rights[3] = false, // Black queenside
-                    (Color::Black, 8, 8) => self.castling_rights[2] = false, // Black kingside
-                    _ => {}
+            MoveKind::KingsideCastle | MoveKind::QueensideCastle => {
+                if let Some((_, rook_to)) = rook_squares {
+                    self.set(rook_to, None);
                 }
             }
+            MoveKind::Normal | MoveKind::DoublePawnPush => {
+                self.set(mv.to, undo.captured);
+            }
         }
-        let piece = self.get(from);
-        // Handle en passant capture
-        if let Some(p) = piece {
-            if p.piece_type == PieceType::Pawn {
-                if let Some(ep) = self.en_passant {
-                    if to == ep
-                        && (from.x - to.x).abs() == 1
-                        && (from.y - to.y).abs() == 1
-                        && self.get(to).is_none()
-                    {
-                        // Remove captured pawn
-                        let cap_y = if p.color == Color::White {
-                            to.y - 1
-                        } else {
-                            to.y + 1
-                        };
-                        self.set(Coordinates { x: to.x, y: cap_y }, None);
-                    }
-                }
+        self.set(mv.from, Some(undo.moved));
+        if let Some((rook_from, _)) = rook_squares {
+            self.set(rook_from, undo.rook);
+        }
+        self.castling_rights = undo.castling_rights;
+        self.en_passant = undo.en_passant;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.color_to_move = undo.moved.color;
+        self.zobrist = undo.zobrist_hash;
+    }
+
+    /// Resolves a from/to pair and optional promotion piece to a fully flagged legal move
+    pub(crate) fn find_legal_move(
+        &self,
+        from: Coordinates,
+        to: Coordinates,
+        promotion: Option<PieceType>,
+    ) -> Result<Move, ChessError> {
+        let candidates: Vec<Move> = self
+            .generate_moves()
+            .into_iter()
+            .filter(|mv| mv.from == from && mv.to == to)
+            .collect();
+        // A plain king move wins over a Chess960 castling move to the same square
+        let first = match candidates
+            .iter()
+            .find(|mv| !mv.is_castle())
+            .or(candidates.first())
+        {
+            Some(mv) => *mv,
+            None => {
+                let mut mv = Move::new(from, to);
+                mv.promotion = promotion;
+                return Err(ChessError::IllegalMove(mv.to_uci()));
             }
+        };
+        if !first.is_promotion() {
+            return Ok(first);
         }
+        // Promotion moves default to Queen
+        let piece = promotion.unwrap_or(PieceType::Queen);
+        candidates
+            .into_iter()
+            .find(|mv| mv.promotion == Some(piece))
+            .ok_or(ChessError::InvalidPromotion(piece))
+    }
+
+    /// Updates the board for a move without checking its legality
+    pub(crate) fn execute_move(&mut self, mv: &Move) -> Undo {
+        let moved = self
+            .get(mv.from)
+            .expect("execute_move requires a piece on the from square");
+        let rook_squares = castling_rook_squares(&self.castling_rights, mv);
+        let en_passant_key = self.en_passant_key();
+        let turn_key = self.turn_key();
+        let undo = Undo {
+            mv: *mv,
+            moved,
+            captured: match mv.kind {
+                MoveKind::EnPassant => self.get(Coordinates::new(mv.to.x, mv.from.y)),
+                MoveKind::KingsideCastle | MoveKind::QueensideCastle => None,
+                _ => self.get(mv.to),
+            },
+            rook: rook_squares.and_then(|(rook_from, _)| self.get(rook_from)),
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            zobrist_hash: self.zobrist,
+        };
+        let mut piece = moved;
+        piece.has_moved = true;
+        // Lift the king and rook first, as Chess960 castling may swap or keep their squares
+        self.set(mv.from, None);
+        if mv.kind == MoveKind::EnPassant {
+            // Remove captured pawn
+            self.set(Coordinates::new(mv.to.x, mv.from.y), None);
+        }
+        if let Some((rook_from, _)) = rook_squares {
+            self.set(rook_from, None);
+        }
+
         // Execute the move
-        self.set(from, None);
-        self.set(to, piece);
+        if let Some(promotion) = mv.promotion {
+            piece.piece_type = promotion;
+        }
+        self.set(mv.to, Some(piece));
+        if let Some((_, rook_to)) = rook_squares {
+            self.set(
+                rook_to,
+                Some(Piece {
+                    piece_type: PieceType::Rook,
+                    color: piece.color,
+                    has_moved: true,
+                }),
+            );
+        }
 
-        // Handle pawn promotion
-        if let Some(mut p) = piece {
-            if p.piece_type == PieceType::Pawn {
-                let promotion_rank = if p.color == Color::White { 8 } else { 1 };
-                if to.y == promotion_rank {
-                    // Use specified promotion piece or default to Queen
-                    p.piece_type = promotion.unwrap_or(PieceType::Queen);
-                    // Validate promotion piece (can only promote to Queen, Rook, Bishop, or Knight)
-                    match p.piece_type {
-                        PieceType::Queen
-                        | PieceType::Rook
-                        | PieceType::Bishop
-                        | PieceType::Knight => {}
-                        _ => return Err("Invalid promotion piece"),
-                    }
-                    self.set(to, Some(p));
+        // Update castling rights when the king moves or a castling rook leaves or is captured
+        if piece.piece_type == PieceType::King {
+            match piece.color {
+                Color::White => {
+                    self.castling_rights[0] = None;
+                    self.castling_rights[1] = None;
+                }
+                Color::Black => {
+                    self.castling_rights[2] = None;
+                    self.castling_rights[3] = None;
                 }
             }
         }
-
-        // Update en passant square
-        if let Some(p) = piece {
-            if p.piece_type == PieceType::Pawn && (from.y - to.y).abs() == 2 {
-                let ep_y = (from.y + to.y) / 2;
-                self.en_passant = Some(Coordinates { x: from.x, y: ep_y });
-            } else {
-                self.en_passant = None;
+        for (index, right) in self.castling_rights.iter_mut().enumerate() {
+            let rank = if index < 2 { 1 } else { 8 };
+            if right.is_some_and(|file| [mv.from, mv.to].contains(&Coordinates::new(file, rank))) {
+                *right = None;
             }
+        }
+        self.update_castling_hash(undo.castling_rights);
+
+        // Update move counters
+        if moved.piece_type == PieceType::Pawn || undo.captured.is_some() {
+            self.halfmove_clock = 0;
         } else {
-            self.en_passant = None;
+            self.halfmove_clock += 1;
+        }
+        if moved.color == Color::Black {
+            self.fullmove_number += 1;
         }
-        self.color_to_move = match self.color_to_move {
-            Color::White => Color::Black,
-            Color::Black => Color::White,
+
+        // Update en passant square
+        self.en_passant = if mv.kind == MoveKind::DoublePawnPush {
+            Some(Coordinates::new(mv.from.x, (mv.from.y + mv.to.y) / 2))
+        } else {
+            None
         };
-        Ok(())
+        self.color_to_move = self.color_to_move.reverse();
+        self.zobrist ^= en_passant_key ^ self.en_passant_key() ^ turn_key ^ self.turn_key();
+        undo
     }
 
     /// Convenience method to promote a pawn to Queen
@@ -154,7 +209,7 @@ impl Board {
         &mut self,
         from: Coordinates,
         to: Coordinates,
-    ) -> Result<(), &'static str> {
+    ) -> Result<(), ChessError> {
         self.apply_move_with_promotion(from, to, Some(PieceType::Queen))
     }
 
@@ -163,7 +218,7 @@ impl Board {
         &mut self,
         from: Coordinates,
         to: Coordinates,
-    ) -> Result<(), &'static str> {
+    ) -> Result<(), ChessError> {
         self.apply_move_with_promotion(from, to, Some(PieceType::Rook))
     }
 
@@ -172,7 +227,7 @@ impl Board {
         &mut self,
         from: Coordinates,
         to: Coordinates,
-    ) -> Result<(), &'static str> {
+    ) -> Result<(), ChessError> {
         self.apply_move_with_promotion(from, to, Some(PieceType::Bishop))
     }
 
@@ -181,7 +236,7 @@ impl Board {
         &mut self,
         from: Coordinates,
         to: Coordinates,
-    ) -> Result<(), &'static str> {
+    ) -> Result<(), ChessError> {
         self.apply_move_with_promotion(from, to, Some(PieceType::Knight))
     }
 
@@ -199,380 +254,154 @@ impl Board {
         }
         None
     }
-    /// Generate all legal moves for the current player.
+    /// Generate all legal moves for the current player as from/to pairs.
+    ///
+    /// Promotions appear once per pawn move; use `generate_moves` to get one entry per promotion piece.
     pub fn generate_legal_moves(&self) -> Vec<(Coordinates, Coordinates)> {
-        let mut legal_moves = Vec::new();
-        for y in 1..=8 {
-            for x in 1..=8 {
-                let from = Coordinates { x, y };
-                if let Some(piece) = self.get(from) {
-                    if piece.color == self.color_to_move {
-                        let pseudo_moves = self.generate_piece_moves(from, piece);
-                        for to in pseudo_moves {
-                            // Check if this move would capture the enemy king (ILLEGAL)
-                            if let Some(target_piece) = self.get(to) {
-                                if target_piece.piece_type == PieceType::King {
-                                    continue; // Skip moves that capture the king
-                                }
-                            }
-
-                            let mut clone = self.clone();
-
-                            // Properly simulate the move including special rules
-                            let moving_piece = clone.get(from);
-                            if let Some(mut piece) = moving_piece {
-                                // Mark piece as moved
-                                piece.has_moved = true;
-
-                                // Handle en passant capture
-                                if piece.piece_type == PieceType::Pawn {
-                                    if let Some(ep) = clone.en_passant {
-                                        if to == ep
-                                            && (from.x - to.x).abs() == 1
-                                            && (from.y - to.y).abs() == 1
-                                            && clone.get(to).is_none()
-                                        {
-                                            // Remove captured pawn
-                                            let cap_y = if piece.color == Color::White {
-                                                to.y - 1
-                                            } else {
-                                                to.y + 1
-                                            };
-                                            clone.set(Coordinates { x: to.x, y: cap_y }, None);
-                                        }
-                                    }
-                                }
-
-                                // Handle castling rook movement
-                                if piece.piece_type == PieceType::King {
-                                    let rank = from.y;
-                                    // Kingside castling
-                                    if from.x == 5 && to.x == 7 {
-                                        clone.set(Coordinates { x: 8, y: rank }, None);
-                                        clone.set(
-                                            Coordinates { x: 6, y: rank },
-                                            Some(Piece {
-                                                piece_type: PieceType::Rook,
-                                                color: piece.color,
-                                                has_moved: true,
-                                            }),
-                                        );
-                                    }
-                                    // Queenside castling
-                                    if from.x == 5 && to.x == 3 {
-                                        clone.set(Coordinates { x: 1, y: rank }, None);
-                                        clone.set(
-                                            Coordinates { x: 4, y: rank },
-                                            Some(Piece {
-                                                piece_type: PieceType::Rook,
-                                                color: piece.color,
-                                                has_moved: true,
-                                            }),
-                                        );
-                                    }
-                                }
-
-                                // Execute the move
-                                clone.set(from, None);
-                                clone.set(to, Some(piece));
-
-                                // Handle pawn promotion
-                                if piece.piece_type == PieceType::Pawn {
-                                    let promotion_rank =
-                                        if piece.color == Color::White { 8 } else { 1 };
-                                    if to.y == promotion_rank {
-                                        let mut promoted_piece = piece;
-                                        promoted_piece.piece_type = PieceType::Queen;
-                                        clone.set(to, Some(promoted_piece));
-                                    }
-                                }
-
-                                // Check if our own king is still safe (DON'T switch color_to_move)
-                                if !clone.is_in_check() {
-                                    legal_moves.push((from, to));
-                                }
-                            }
-                        }
-                    }
+        let mut legal_moves: Vec<(Coordinates, Coordinates)> =
+            self.generate_moves().iter().map(Move::to_tuple).collect();
+        legal_moves.dedup();
+        legal_moves
+    }
+
+    /// Generate all legal moves for the current player.
+    pub fn generate_moves(&self) -> Vec<Move> {
+        movegen::legal_moves(self)
+    }
+
+    /// Classifies a pseudo-legal destination into one move per promotion piece, or a single move.
+    pub(crate) fn build_moves(
+        &self,
+        from: Coordinates,
+        to: Coordinates,
+        piece: Piece,
+    ) -> Vec<Move> {
+        let mut mv = Move::new(from, to);
+        mv.captured = self.get(to).map(|target| target.piece_type);
+        match piece.piece_type {
+            PieceType::King if (to.x - from.x).abs() == 2 => {
+                mv.kind = if to.x > from.x {
+                    MoveKind::KingsideCastle
+                } else {
+                    MoveKind::QueensideCastle
+                };
+            }
+            PieceType::Pawn => {
+                if (to.y - from.y).abs() == 2 {
+                    mv.kind = MoveKind::DoublePawnPush;
+                } else if from.x != to.x && mv.captured.is_none() {
+                    mv.kind = MoveKind::EnPassant;
+                    mv.captured = Some(PieceType::Pawn);
+                }
+                let promotion_rank = if piece.color == Color::White { 8 } else { 1 };
+                if to.y == promotion_rank {
+                    return [
+                        PieceType::Queen,
+                        PieceType::Rook,
+                        PieceType::Bishop,
+                        PieceType::Knight,
+                    ]
+                    .iter()
+                    .map(|&promotion| Move {
+                        promotion: Some(promotion),
+                        ..mv
+                    })
+                    .collect();
                 }
             }
+            _ => {}
         }
-        legal_moves
+        vec![mv]
     }
 
     /// Check if the current player is in check.
     pub fn is_in_check(&self) -> bool {
-        let king_pos = match self.find_king(self.color_to_move) {
-            Some(pos) => pos,
-            None => return false,
-        };
-        let opponent = match self.color_to_move {
-            Color::White => Color::Black,
-            Color::Black => Color::White,
-        };
-        for row in 0..8 {
-            for col in 0..8 {
-                if let Some(piece) = self.squares[row][col] {
-                    if piece.color == opponent {
-                        let from = Coordinates {
-                            x: (col + 1) as i8,
-                            y: (row + 1) as i8,
-                        };
-                        let moves = self.generate_piece_moves(from, piece);
-                        if moves.contains(&king_pos) {
-                            return true;
-                        }
-                    }
-                }
-            }
+        self.is_color_in_check(self.color_to_move)
+    }
+
+    /// Check if the king of the given color is attacked.
+    fn is_color_in_check(&self, color: Color) -> bool {
+        match squares(self.bitboards.pieces_of(color, PieceType::King)).next() {
+            Some(king) => self.is_square_attacked(Coordinates::from_index(king), color.reverse()),
+            None => false,
         }
-        false
+    }
+
+    /// Checks if any piece of `by_color` attacks the square
+    pub fn is_square_attacked(&self, square: Coordinates, by_color: Color) -> bool {
+        self.attacker_set(square, by_color) != 0
+    }
+
+    /// Gets the squares of the pieces of `color` attacking the square, in a1 to h8 order
+    pub fn attackers_to(&self, square: Coordinates, color: Color) -> Vec<Coordinates> {
+        squares(self.attacker_set(square, color))
+            .map(Coordinates::from_index)
+            .collect()
+    }
+
+    /// Gets the attackers of a square as a bitboard, or none for a square off the board
+    fn attacker_set(&self, square: Coordinates, color: Color) -> u64 {
+        if !square.is_valid() {
+            return 0;
+        }
+        let index = square.to_index().expect("valid coordinates");
+        self.bitboards
+            .attackers_to(index, color, self.bitboards.occupied())
     }
 
     /// Check if the current player is in checkmate.
     pub fn is_checkmate(&self) -> bool {
-        self.is_in_check() && self.generate_legal_moves().is_empty()
+        self.is_in_check() && self.generate_moves().is_empty()
     }
 
     /// Check if the current player is in stalemate.
     pub fn is_stalemate(&self) -> bool {
-        !self.is_in_check() && self.generate_legal_moves().is_empty()
+        !self.is_in_check() && self.generate_moves().is_empty()
     }
 
-    /// Generate all moves for a piece at a given position (ignores check).
-    fn generate_piece_moves(&self, from: Coordinates, piece: Piece) -> Vec<Coordinates> {
-        let mut moves = Vec::new();
-        match piece.piece_type {
-            PieceType::Pawn => {
-                let dir = if piece.color == Color::White { 1 } else { -1 };
-                let next_y = from.y + dir;
-                if next_y >= 1 && next_y <= 8 {
-                    // Forward
-                    let forward = Coordinates {
-                        x: from.x,
-                        y: next_y,
-                    };
-                    if self.get(forward).is_none() {
-                        moves.push(forward);
-                        // Double move
-                        let start_row = if piece.color == Color::White { 2 } else { 7 };
-                        if from.y == start_row {
-                            let double_forward = Coordinates {
-                                x: from.x,
-                                y: from.y + 2 * dir,
-                            };
-                            if self.get(double_forward).is_none() {
-                                moves.push(double_forward);
-                            }
-                        }
-                    }
-                    // Captures
-                    for dx in [-1, 1].iter() {
-                        let nx = from.x + dx;
-                        if nx >= 1 && nx <= 8 {
-                            let capture = Coordinates { x: nx, y: next_y };
-                            if let Some(target) = self.get(capture) {
-                                if target.color != piece.color {
-                                    moves.push(capture);
-                                }
-                            } else if let Some(ep) = self.en_passant {
-                                if capture == ep {
-                                    moves.push(capture);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            PieceType::Knight => {
-                let knight_moves = [
-                    (2, 1),
-                    (1, 2),
-                    (-1, 2),
-                    (-2, 1),
-                    (-2, -1),
-                    (-1, -2),
-                    (1, -2),
-                    (2, -1),
-                ];
-                for (dx, dy) in knight_moves.iter() {
-                    let nx = from.x + dx;
-                    let ny = from.y + dy;
-                    if nx >= 1 && nx <= 8 && ny >= 1 && ny <= 8 {
-                        let to = Coordinates { x: nx, y: ny };
-                        if let Some(target) = self.get(to) {
-                            if target.color != piece.color {
-                                moves.push(to);
-                            }
-                        } else {
-                            moves.push(to);
-                        }
-                    }
-                }
-            }
-            PieceType::Bishop => {
-                let directions = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
-                for (dx, dy) in directions.iter() {
-                    let mut nx = from.x + dx;
-                    let mut ny = from.y + dy;
-                    while nx >= 1 && nx <= 8 && ny >= 1 && ny <= 8 {
-                        let to = Coordinates { x: nx, y: ny };
-                        if let Some(target) = self.get(to) {
-                            if target.color != piece.color {
-                                moves.push(to);
-                            }
-                            break;
-                        } else {
-                            moves.push(to);
-                        }
-                        nx += dx;
-                        ny += dy;
-                    }
-                }
-            }
-            PieceType::Rook => {
-                let directions = [(1, 0), (-1, 0), (0, 1), (0, -1)];
-                for (dx, dy) in directions.iter() {
-                    let mut nx = from.x + dx;
-                    let mut ny = from.y + dy;
-                    while nx >= 1 && nx <= 8 && ny >= 1 && ny <= 8 {
-                        let to = Coordinates { x: nx, y: ny };
-                        if let Some(target) = self.get(to) {
-                            if target.color != piece.color {
-                                moves.push(to);
-                            }
-                            break;
-                        } else {
-                            moves.push(to);
-                        }
-                        nx += dx;
-                        ny += dy;
-                    }
-                }
-            }
-            PieceType::Queen => {
-                let directions = [
-                    (1, 1),
-                    (1, -1),
-                    (-1, 1),
-                    (-1, -1),
-                    (1, 0),
-                    (-1, 0),
-                    (0, 1),
-                    (0, -1),
-                ];
-                for (dx, dy) in directions.iter() {
-                    let mut nx = from.x + dx;
-                    let mut ny = from.y + dy;
-                    while nx >= 1 && nx <= 8 && ny >= 1 && ny <= 8 {
-                        let to = Coordinates { x: nx, y: ny };
-                        if let Some(target) = self.get(to) {
-                            if target.color != piece.color {
-                                moves.push(to);
-                            }
-                            break;
-                        } else {
-                            moves.push(to);
-                        }
-                        nx += dx;
-                        ny += dy;
-                    }
-                }
-            }
-            PieceType::King => {
-                let king_moves = [
-                    (1, 0),
-                    (1, 1),
-                    (0, 1),
-                    (-1, 1),
-                    (-1, 0),
-                    (-1, -1),
-                    (0, -1),
-                    (1, -1),
-                ];
-                for (dx, dy) in king_moves.iter() {
-                    let nx = from.x + dx;
-                    let ny = from.y + dy;
-                    if nx >= 1 && nx <= 8 && ny >= 1 && ny <= 8 {
-                        let to = Coordinates { x: nx, y: ny };
-                        if let Some(target) = self.get(to) {
-                            if target.color != piece.color {
-                                moves.push(to);
-                            }
-                        } else {
-                            moves.push(to);
-                        }
-                    }
-                }
-                // Castling
-                let (rank, kingside, queenside) = match piece.color {
-                    Color::White => (1, self.castling_rights[0], self.castling_rights[1]),
-                    Color::Black => (8, self.castling_rights[2], self.castling_rights[3]),
-                };
-                // Helper to check if a square is attacked
-                let is_attacked = |sq: Coordinates| {
-                    let mut clone = self.clone();
-                    clone.color_to_move = match piece.color {
-                        Color::White => Color::Black,
-                        Color::Black => Color::White,
-                    };
-                    for y in 1..=8 {
-                        for x in 1..=8 {
-                            let from = Coordinates { x, y };
-                            if let Some(attacker) = clone.get(from) {
-                                if attacker.color == clone.color_to_move {
-                                    if attacker.piece_type == PieceType::King {
-                                        // King attacks adjacent squares only
-                                        if (from.x - sq.x).abs() <= 1 && (from.y - sq.y).abs() <= 1
-                                        {
-                                            return true;
-                                        }
-                                    } else {
-                                        let attacks = clone.generate_piece_moves(from, attacker);
-                                        if attacks.contains(&sq) {
-                                            return true;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    false
-                };
-                // Kingside
-                if kingside
-                    && self.get(Coordinates { x: 6, y: rank }).is_none()
-                    && self.get(Coordinates { x: 7, y: rank }).is_none()
-                    && self.get(Coordinates { x: 8, y: rank }).map_or(false, |r| {
-                        r.piece_type == PieceType::Rook && r.color == piece.color
-                    })
-                    && !is_attacked(Coordinates { x: 5, y: rank })
-                    && !is_attacked(Coordinates { x: 6, y: rank })
-                    && !is_attacked(Coordinates { x: 7, y: rank })
-                {
-                    moves.push(Coordinates { x: 7, y: rank });
-                }
-                // Queenside
-                if queenside
-                    && self.get(Coordinates { x: 4, y: rank }).is_none()
-                    && self.get(Coordinates { x: 3, y: rank }).is_none()
-                    && self.get(Coordinates { x: 2, y: rank }).is_none()
-                    && self.get(Coordinates { x: 1, y: rank }).map_or(false, |r| {
-                        r.piece_type == PieceType::Rook && r.color == piece.color
-                    })
-                    && !is_attacked(Coordinates { x: 5, y: rank })
-                    && !is_attacked(Coordinates { x: 4, y: rank })
-                    && !is_attacked(Coordinates { x: 3, y: rank })
-                {
-                    moves.push(Coordinates { x: 3, y: rank });
-                }
-            }
+    /// Checks if neither side has the material to checkmate, such as K vs K, K and a minor piece vs K,
+    /// or kings with bishops all on one square color
+    pub fn has_insufficient_material(&self) -> bool {
+        !self.can_possibly_win(Color::White) && !self.can_possibly_win(Color::Black)
+    }
+
+    /// Checks if `color` has the material to checkmate with some series of legal moves.
+    ///
+    /// Under FIDE rules a player who runs out of time only loses if this holds for the opponent.
+    pub fn can_possibly_win(&self, color: Color) -> bool {
+        let bitboards = &self.bitboards;
+        let own = bitboards.color(color);
+        let heavy = bitboards.pieces(PieceType::Pawn)
+            | bitboards.pieces(PieceType::Rook)
+            | bitboards.pieces(PieceType::Queen);
+        if own & heavy != 0 {
+            return true;
+        }
+        if own & bitboards.pieces(PieceType::Knight) != 0 {
+            // A lone knight needs an enemy piece other than a queen to box the king in
+            let blockers = bitboards.color(color.reverse())
+                & !bitboards.pieces(PieceType::King)
+                & !bitboards.pieces(PieceType::Queen);
+            return own.count_ones() > 2 || blockers != 0;
         }
-        moves
+        if own & bitboards.pieces(PieceType::Bishop) != 0 {
+            // Bishops on a single square color can only mate with help from pawns or knights
+            let bishops = bitboards.pieces(PieceType::Bishop);
+            let one_color = bishops & DARK_SQUARES == 0 || bishops & !DARK_SQUARES == 0;
+            let helpers = bitboards.pieces(PieceType::Pawn) | bitboards.pieces(PieceType::Knight);
+            return !one_color || helpers != 0;
+        }
+        false
     }
 }
 
+use crate::chess::board::bitboard::{squares, Bitboards, DARK_SQUARES};
 use crate::chess::board::coordinates::Coordinates;
+use crate::chess::board::fen::{castling_field, parse_fen, FenMode};
+use crate::chess::board::movegen;
+use crate::chess::board::moves::{Move, MoveKind, Undo};
+use crate::chess::board::zobrist::piece_key;
+use crate::chess::error::ChessError;
 use crate::chess::piece::piece::{Color, PieceType};
 
 use std::fmt;
@@ -604,109 +433,57 @@ impl fmt::Display for Piece {
     }
 }
 
+/// FEN of the standard starting position
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// A chess position.
+///
+/// The pieces, side to move, castling rights and en passant square are kept in sync with the
+/// bitboards and the Zobrist hash, so they are only changed through `set` and the `set_*` methods.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Board {
-    pub squares: [[Option<Piece>; 8]; 8],
-    pub color_to_move: Color,
-    pub castling_rights: [bool; 4], // [w_kingside, w_queenside, b_kingside, b_queenside]
-    pub en_passant: Option<Coordinates>,
+    /// Pieces indexed `[rank - 1][file - 1]`
+    pub(crate) squares: [[Option<Piece>; 8]; 8],
+    pub(crate) color_to_move: Color,
+    /// Rook file of each castling right, or `None` once it is lost: [w_kingside, w_queenside, b_kingside, b_queenside]
+    pub(crate) castling_rights: [Option<i8>; 4],
+    pub(crate) en_passant: Option<Coordinates>,
     pub halfmove_clock: u32,
     pub fullmove_number: u32,
+    bitboards: Bitboards,
+    pub(crate) zobrist: u64,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Board {
     pub fn new() -> Self {
-        Self {
+        let mut board = Self {
             squares: [[None; 8]; 8],
             color_to_move: Color::White,
-            castling_rights: [true, true, true, true],
+            castling_rights: [Some(8), Some(1), Some(8), Some(1)],
             en_passant: None,
             halfmove_clock: 0,
             fullmove_number: 1,
-        }
+            bitboards: Bitboards::default(),
+            zobrist: 0,
+        };
+        board.zobrist = board.compute_zobrist_hash();
+        board
     }
 
-    pub fn from_fen(fen: &str) -> Result<Self, &'static str> {
-        let mut board = Board::new();
-        let parts: Vec<&str> = fen.split_whitespace().collect();
-        if parts.len() < 4 {
-            return Err("Invalid FEN: not enough parts");
-        }
-        // Piece placement
-        let mut rank = 8;
-        let mut file = 1;
-        for c in parts[0].chars() {
-            match c {
-                '/' => {
-                    rank -= 1;
-                    file = 1;
-                }
-                '1'..='8' => {
-                    file += c.to_digit(10).unwrap() as i8;
-                }
-                'p' | 'n' | 'b' | 'r' | 'q' | 'k' | 'P' | 'N' | 'B' | 'R' | 'Q' | 'K' => {
-                    let (piece_type, color) = match c {
-                        'p' => (PieceType::Pawn, Color::Black),
-                        'n' => (PieceType::Knight, Color::Black),
-                        'b' => (PieceType::Bishop, Color::Black),
-                        'r' => (PieceType::Rook, Color::Black),
-                        'q' => (PieceType::Queen, Color::Black),
-                        'k' => (PieceType::King, Color::Black),
-                        'P' => (PieceType::Pawn, Color::White),
-                        'N' => (PieceType::Knight, Color::White),
-                        'B' => (PieceType::Bishop, Color::White),
-                        'R' => (PieceType::Rook, Color::White),
-                        'Q' => (PieceType::Queen, Color::White),
-                        'K' => (PieceType::King, Color::White),
-                        _ => unreachable!(),
-                    };
-                    let piece = Piece {
-                        piece_type,
-                        color,
-                        has_moved: false,
-                    };
-                    board.set(Coordinates { x: file, y: rank }, Some(piece));
-                    file += 1;
-                }
-                _ => return Err("Invalid FEN: invalid character in piece placement"),
-            }
-        }
-        // The rest of the FEN parsing logic (active color, castling rights, en passant, etc.) follows here, outside the piece placement loop.
-        // Active color
-        board.color_to_move = match parts[1] {
-            "w" => Color::White,
-            "b" => Color::Black,
-            _ => return Err("Invalid FEN: invalid active color"),
-        };
-        // Castling rights
-        board.castling_rights = [false; 4];
-        if parts[2].contains('K') {
-            board.castling_rights[0] = true;
-        }
-        if parts[2].contains('Q') {
-            board.castling_rights[1] = true;
-        }
-        if parts[2].contains('k') {
-            board.castling_rights[2] = true;
-        }
-        if parts[2].contains('q') {
-            board.castling_rights[3] = true;
-        }
-        // En passant
-        if parts[3] != "-" {
-            let file = (parts[3].as_bytes()[0] - b'a' + 1) as i8;
-            let rank = (parts[3].as_bytes()[1] - b'1' + 1) as i8;
-            board.en_passant = Some(Coordinates { x: file, y: rank });
-        }
-        // Halfmove clock
-        if parts.len() > 4 {
-            board.halfmove_clock = parts[4].parse().unwrap_or(0);
-        }
-        // Fullmove number
-        if parts.len() > 5 {
-            board.fullmove_number = parts[5].parse().unwrap_or(1);
-        }
-        Ok(board)
+    /// Creates a board set up in the standard starting position
+    pub fn starting_position() -> Self {
+        Self::from_fen(STARTING_FEN).expect("starting FEN is valid")
+    }
+
+    /// Parses a FEN record, checking its syntax but not the legality of the position
+    pub fn from_fen(fen: &str) -> Result<Self, ChessError> {
+        Ok(parse_fen(fen, FenMode::Basic)?)
     }
 
     pub fn to_fen(&self) -> String {
@@ -754,23 +531,7 @@ impl Board {
         });
         // Castling rights
         fen.push(' ');
-        let mut rights = String::new();
-        if self.castling_rights[0] {
-            rights.push('K');
-        }
-        if self.castling_rights[1] {
-            rights.push('Q');
-        }
-        if self.castling_rights[2] {
-            rights.push('k');
-        }
-        if self.castling_rights[3] {
-            rights.push('q');
-        }
-        if rights.is_empty() {
-            rights.push('-');
-        }
-        fen.push_str(&rights);
+        fen.push_str(&castling_field(self, false));
         // En passant
         fen.push(' ');
         if let Some(ep) = self.en_passant {
@@ -797,7 +558,59 @@ impl Board {
 
     pub fn set(&mut self, coord: Coordinates, piece: Option<Piece>) {
         let (x, y) = ((coord.x - 1) as usize, (coord.y - 1) as usize);
+        let index = y * 8 + x;
+        if let Some(old) = self.squares[y][x] {
+            self.zobrist ^= piece_key(old, index);
+        }
+        if let Some(new) = piece {
+            self.zobrist ^= piece_key(new, index);
+        }
         self.squares[y][x] = piece;
+        self.bitboards.set(index, piece);
+    }
+
+    /// Gets the pieces indexed `[rank - 1][file - 1]`
+    pub fn squares(&self) -> &[[Option<Piece>; 8]; 8] {
+        &self.squares
+    }
+
+    /// Gets the side to move
+    pub fn color_to_move(&self) -> Color {
+        self.color_to_move
+    }
+
+    /// Sets the side to move
+    pub fn set_color_to_move(&mut self, color: Color) {
+        self.color_to_move = color;
+        self.zobrist = self.compute_zobrist_hash();
+    }
+
+    /// Gets the rook file of each castling right, or `None` once it is lost:
+    /// [w_kingside, w_queenside, b_kingside, b_queenside]
+    pub fn castling_rights(&self) -> [Option<i8>; 4] {
+        self.castling_rights
+    }
+
+    /// Sets the castling rights, given as in `castling_rights`
+    pub fn set_castling_rights(&mut self, castling_rights: [Option<i8>; 4]) {
+        self.castling_rights = castling_rights;
+        self.zobrist = self.compute_zobrist_hash();
+    }
+
+    /// Gets the square a pawn can capture en passant on
+    pub fn en_passant(&self) -> Option<Coordinates> {
+        self.en_passant
+    }
+
+    /// Sets the square a pawn can capture en passant on
+    pub fn set_en_passant(&mut self, en_passant: Option<Coordinates>) {
+        self.en_passant = en_passant;
+        self.zobrist = self.compute_zobrist_hash();
+    }
+
+    /// Gets the per-piece-type and per-color square sets of the position
+    pub fn bitboards(&self) -> &Bitboards {
+        &self.bitboards
     }
 
     pub fn display(&self) {
@@ -814,3 +627,27 @@ impl Board {
         println!("   a  b  c  d  e  f  g  h");
     }
 }
+
+/// Gets the start and end squares of the rook in a castling move, given the castling rights before it
+pub(crate) fn castling_rook_squares(
+    castling_rights: &[Option<i8>; 4],
+    mv: &Move,
+) -> Option<(Coordinates, Coordinates)> {
+    let side = match mv.kind {
+        MoveKind::KingsideCastle => 0,
+        MoveKind::QueensideCastle => 1,
+        _ => return None,
+    };
+    let (index, rank) = if mv.from.y == 1 {
+        (side, 1)
+    } else {
+        (2 + side, 8)
+    };
+    let rook_file = castling_rights[index]?;
+    // The rook ends next to the king on the side it came from
+    let rook_to = if side == 0 { 6 } else { 4 };
+    Some((
+        Coordinates::new(rook_file, rank),
+        Coordinates::new(rook_to, rank),
+    ))
+}
@@ -0,0 +1,69 @@
+use crate::chess::board::board::Board;
+
+/// Knight placements among the five squares left after the bishops and queen, by Scharnagl digit
+const KNIGHT_PLACEMENTS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+impl Board {
+    /// Creates the Chess960 starting position with the given Scharnagl index (0 to 959).
+    ///
+    /// Index 518 is the standard starting position. Returns `None` for an index above 959.
+    pub fn chess960_position(index: u32) -> Option<Board> {
+        if index >= 960 {
+            return None;
+        }
+        let mut rank: [Option<char>; 8] = [None; 8];
+        let mut n = index as usize;
+        // Light-squared bishop on b, d, f or h, then dark-squared bishop on a, c, e or g
+        rank[2 * (n % 4) + 1] = Some('B');
+        n /= 4;
+        rank[2 * (n % 4)] = Some('B');
+        n /= 4;
+        place_on_empty(&mut rank, n % 6, 'Q');
+        n /= 6;
+        // Place the second knight first so the first one's empty-square index is unchanged
+        let (first, second) = KNIGHT_PLACEMENTS[n];
+        place_on_empty(&mut rank, second, 'N');
+        place_on_empty(&mut rank, first, 'N');
+        // The king goes between the rooks on the three squares left
+        for piece in ['R', 'K', 'R'] {
+            place_on_empty(&mut rank, 0, piece);
+        }
+
+        let white: String = rank.iter().flatten().collect();
+        let rook_files: String = white
+            .char_indices()
+            .filter(|&(_, piece)| piece == 'R')
+            .rev()
+            .map(|(file, _)| (b'A' + file as u8) as char)
+            .collect();
+        let fen = format!(
+            "{}/pppppppp/8/8/8/8/PPPPPPPP/{} w {}{} - 0 1",
+            white.to_ascii_lowercase(),
+            white,
+            rook_files,
+            rook_files.to_ascii_lowercase()
+        );
+        Some(Board::from_fen(&fen).expect("generated Chess960 FEN is valid"))
+    }
+}
+
+/// Puts a piece on the `n`th still empty square of the back rank
+fn place_on_empty(rank: &mut [Option<char>; 8], n: usize, piece: char) {
+    let square = rank
+        .iter_mut()
+        .filter(|square| square.is_none())
+        .nth(n)
+        .expect("enough empty squares");
+    *square = Some(piece);
+}
@@ -1,7 +1,8 @@
 use super::notation::Notation;
+use crate::chess::error::ChessError;
 
 /// Chess board coordinates (1-indexed: a1 = 1,1)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Coordinates {
     /// File (a-h = 1-8)
     pub x: i8,
@@ -31,36 +32,38 @@ impl Coordinates {
     }
 
     /// Parses coordinates from "x,y" string
-    pub fn from_string(input: &str) -> Result<Coordinates, &'static str> {
+    pub fn from_string(input: &str) -> Result<Coordinates, ChessError> {
         let parts: Vec<&str> = input.split(',').collect();
         if parts.len() != 2 {
-            return Err("Input should be in the format 'x,y'");
+            return Err(ChessError::InvalidCoordinates(input.to_string()));
         }
         let x = parts[0]
             .trim()
             .parse::<i8>()
-            .map_err(|_| "Invalid x coordinate")?;
+            .map_err(|_| ChessError::InvalidCoordinates(input.to_string()))?;
         let y = parts[1]
             .trim()
             .parse::<i8>()
-            .map_err(|_| "Invalid y coordinate")?;
-        if x < 1 || x > 8 || y < 1 || y > 8 {
-            return Err("Coordinates should be between 1 and 8");
+            .map_err(|_| ChessError::InvalidCoordinates(input.to_string()))?;
+        if !(1..=8).contains(&x) || !(1..=8).contains(&y) {
+            return Err(ChessError::InvalidCoordinates(input.to_string()));
         }
         Ok(Coordinates::new(x, y))
     }
 
     /// Creates a `Coordinates` instance from a string representation of notations.
-    pub fn from_notation_string(s: &str) -> Result<Self, &'static str> {
-        if s.len() != 2 {
-            return Err("Invalid notation string");
-        }
-
-        let file = s.chars().nth(0).unwrap().to_ascii_lowercase();
-        let rank = s.chars().nth(1).unwrap().to_digit(10).unwrap();
+    pub fn from_notation_string(s: &str) -> Result<Self, ChessError> {
+        let mut chars = s.chars();
+        let (file, rank) = match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => (file.to_ascii_lowercase(), rank),
+            _ => return Err(ChessError::InvalidNotation(s.to_string())),
+        };
+        let rank = rank
+            .to_digit(10)
+            .ok_or_else(|| ChessError::InvalidNotation(s.to_string()))?;
 
-        if file < 'a' || file > 'h' || rank < 1 || rank > 8 {
-            return Err("Invalid notation string");
+        if !('a'..='h').contains(&file) || !(1..=8).contains(&rank) {
+            return Err(ChessError::InvalidNotation(s.to_string()));
         }
 
         let x = file as i8 - 'a' as i8 + 1;
@@ -69,26 +72,37 @@ impl Coordinates {
         Ok(Self { x, y })
     }
     /// Converts the `Coordinates` instance to a string representation.
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         format!("{}{}", self.x + 1, self.y + 1)
     }
 
     /// Creates a `Coordinates` instance from a `Notation` instance.
     /// Returns an error if the notation is not valid.
-    pub fn from_notation(notation: Notation) -> Result<Coordinates, &'static str> {
+    pub fn from_notation(notation: Notation) -> Result<Coordinates, ChessError> {
         let x = match notation.file {
-            'a'..='h' => (notation.file as u8 - 'a' as u8 + 1) as i8,
-            _ => return Err("Invalid file"),
+            'a'..='h' => (notation.file as u8 - b'a' + 1) as i8,
+            _ => {
+                return Err(ChessError::InvalidNotation(format!(
+                    "{}{}",
+                    notation.file, notation.rank
+                )))
+            }
         };
         let y = match notation.rank {
-            '1'..='8' => (notation.rank as u8 - '1' as u8 + 1) as i8,
-            _ => return Err("Invalid rank"),
+            '1'..='8' => (notation.rank as u8 - b'1' + 1) as i8,
+            _ => {
+                return Err(ChessError::InvalidNotation(format!(
+                    "{}{}",
+                    notation.file, notation.rank
+                )))
+            }
         };
         Ok(Coordinates::new(x, y))
     }
     /// Converts the `Coordinates` instance to a `Notation` instance.
     /// Returns an error if the coordinates are not valid.
-    pub fn to_notation(&self) -> Result<Notation, &'static str> {
+    pub fn to_notation(&self) -> Result<Notation, ChessError> {
         let file = match self.x {
             1 => 'a',
             2 => 'b',
@@ -98,7 +112,7 @@ impl Coordinates {
             6 => 'f',
             7 => 'g',
             8 => 'h',
-            _ => return Err("Invalid x coordinate"),
+            _ => return Err(self.invalid()),
         };
         let rank = match self.y {
             1 => '1',
@@ -109,15 +123,15 @@ impl Coordinates {
             6 => '6',
             7 => '7',
             8 => '8',
-            _ => return Err("Invalid y coordinate"),
+            _ => return Err(self.invalid()),
         };
         Ok(Notation { file, rank })
     }
 
     /// Converts the `Coordinates` instance to an index in a 1D array representation of the board.
-    pub fn to_index(&self) -> Result<usize, &'static str> {
+    pub fn to_index(&self) -> Result<usize, ChessError> {
         if self.y < 1 || self.y > 8 {
-            return Err("y coordinate is out of bounds");
+            return Err(self.invalid());
         }
         let x = (self.x - 1) as usize;
         let y = (self.y - 1) as usize;
@@ -129,6 +143,11 @@ impl Coordinates {
         Coordinates::new((index % 8 + 1) as i8, (index / 8 + 1) as i8)
     }
 
+    /// Builds the error reported for these coordinates when they are off the board
+    fn invalid(&self) -> ChessError {
+        ChessError::InvalidCoordinates(format!("{},{}", self.x, self.y))
+    }
+
     /// Checks if the `Coordinates` instance represents a valid position on the board.
     pub fn is_valid(&self) -> bool {
         self.x >= 1 && self.x <= 8 && self.y >= 1 && self.y <= 8
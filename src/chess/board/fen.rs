@@ -0,0 +1,530 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::chess::board::board::{Board, Piece};
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::error::ChessError;
+use crate::chess::piece::piece::{Color, PieceType};
+
+/// Field of a FEN record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FenField {
+    PiecePlacement,
+    ActiveColor,
+    CastlingRights,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
+impl fmt::Display for FenField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FenField::PiecePlacement => "piece placement",
+            FenField::ActiveColor => "active color",
+            FenField::CastlingRights => "castling rights",
+            FenField::EnPassant => "en passant square",
+            FenField::HalfmoveClock => "halfmove clock",
+            FenField::FullmoveNumber => "fullmove number",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Reason a FEN record was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FenErrorKind {
+    /// The record ends before this field
+    MissingField,
+    /// The record has more than six fields
+    TooManyFields,
+    /// A character that is not allowed in this field
+    InvalidCharacter(char),
+    /// A rank describes more than eight squares
+    RankTooLong,
+    /// A rank describes fewer than eight squares
+    RankTooShort,
+    /// Piece placement does not describe exactly eight ranks
+    WrongRankCount,
+    /// Active color is neither "w" nor "b"
+    InvalidActiveColor,
+    /// A castling right is listed twice
+    DuplicateCastlingRight,
+    /// A castling right without the king and rook on their home squares
+    InvalidCastlingRight,
+    /// En passant field is neither "-" nor a square
+    InvalidEnPassantSquare,
+    /// En passant square that no double pawn push could have produced
+    ImpossibleEnPassant,
+    /// A move counter that is not a valid number
+    InvalidNumber,
+    /// A side does not have exactly one king
+    InvalidKingCount(Color),
+    /// A pawn on the first or eighth rank
+    PawnOnBackRank,
+    /// The side that just moved is left in check
+    OpponentInCheck,
+}
+
+impl FenErrorKind {
+    /// Gets a short description of the problem
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FenErrorKind::MissingField => "Invalid FEN: not enough parts",
+            FenErrorKind::TooManyFields => "Invalid FEN: too many parts",
+            FenErrorKind::InvalidCharacter(_) => "Invalid FEN: invalid character",
+            FenErrorKind::RankTooLong => "Invalid FEN: rank has more than 8 squares",
+            FenErrorKind::RankTooShort => "Invalid FEN: rank has fewer than 8 squares",
+            FenErrorKind::WrongRankCount => "Invalid FEN: piece placement needs 8 ranks",
+            FenErrorKind::InvalidActiveColor => "Invalid FEN: invalid active color",
+            FenErrorKind::DuplicateCastlingRight => "Invalid FEN: duplicate castling right",
+            FenErrorKind::InvalidCastlingRight => {
+                "Invalid FEN: castling right without king and rook on their home squares"
+            }
+            FenErrorKind::InvalidEnPassantSquare => "Invalid FEN: invalid en passant square",
+            FenErrorKind::ImpossibleEnPassant => "Invalid FEN: impossible en passant square",
+            FenErrorKind::InvalidNumber => "Invalid FEN: invalid move counter",
+            FenErrorKind::InvalidKingCount(_) => "Invalid FEN: each side needs exactly one king",
+            FenErrorKind::PawnOnBackRank => "Invalid FEN: pawn on the first or eighth rank",
+            FenErrorKind::OpponentInCheck => "Invalid FEN: side not to move is in check",
+        }
+    }
+}
+
+/// Error from parsing a FEN record: which field, at which character offset, and why
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FenError {
+    /// Field containing the problem
+    pub field: FenField,
+    /// Character offset into the FEN string
+    pub offset: usize,
+    /// What is wrong
+    pub kind: FenErrorKind,
+}
+
+impl FenError {
+    fn new(field: FenField, offset: usize, kind: FenErrorKind) -> FenError {
+        FenError {
+            field,
+            offset,
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (in {} at offset {})",
+            self.kind.as_str(),
+            self.field,
+            self.offset
+        )?;
+        match self.kind {
+            FenErrorKind::InvalidCharacter(c) => write!(f, ": '{}'", c),
+            FenErrorKind::InvalidKingCount(color) => write!(f, ": {:?}", color),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Error for FenError {}
+
+/// How strictly a FEN record is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FenMode {
+    /// Syntax only; the move counters may be omitted
+    Basic,
+    /// Syntax and position legality; all six fields required
+    Strict,
+    /// Like `Strict`, but drops castling rights and en passant squares that cannot apply
+    Lenient,
+}
+
+impl Board {
+    /// Parses a FEN record, rejecting anything that is not a legal, fully specified position
+    pub fn from_fen_strict(fen: &str) -> Result<Self, ChessError> {
+        Ok(parse_fen(fen, FenMode::Strict)?)
+    }
+
+    /// Parses a FEN record, repairing inconsistent castling rights, en passant squares and counters
+    pub fn from_fen_lenient(fen: &str) -> Result<Self, ChessError> {
+        Ok(parse_fen(fen, FenMode::Lenient)?)
+    }
+
+    /// Formats the position as Shredder-FEN, naming every castling right by its rook file (e.g. "HAha")
+    pub fn to_shredder_fen(&self) -> String {
+        let fen = self.to_fen();
+        let mut fields: Vec<&str> = fen.split(' ').collect();
+        let castling = castling_field(self, true);
+        fields[2] = &castling;
+        fields.join(" ")
+    }
+}
+
+/// Parses a FEN record with the given level of checking
+pub(crate) fn parse_fen(fen: &str, mode: FenMode) -> Result<Board, FenError> {
+    // FEN is ASCII, so byte offsets below are also character offsets
+    if let Some((offset, c)) = fen.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+        return Err(FenError::new(
+            FenField::PiecePlacement,
+            offset,
+            FenErrorKind::InvalidCharacter(c),
+        ));
+    }
+    let fields = split_fields(fen);
+    let required = if mode == FenMode::Strict { 6 } else { 4 };
+    let field_order = [
+        FenField::PiecePlacement,
+        FenField::ActiveColor,
+        FenField::CastlingRights,
+        FenField::EnPassant,
+        FenField::HalfmoveClock,
+        FenField::FullmoveNumber,
+    ];
+    if fields.len() < required {
+        return Err(FenError::new(
+            field_order[fields.len()],
+            fen.len(),
+            FenErrorKind::MissingField,
+        ));
+    }
+    if fields.len() > 6 && mode != FenMode::Basic {
+        return Err(FenError::new(
+            FenField::FullmoveNumber,
+            fields[6].0,
+            FenErrorKind::TooManyFields,
+        ));
+    }
+
+    let mut board = Board::new();
+    parse_placement(&mut board, fields[0])?;
+
+    // Active color
+    let (offset, color) = fields[1];
+    board.color_to_move = match color {
+        "w" => Color::White,
+        "b" => Color::Black,
+        _ => {
+            return Err(FenError::new(
+                FenField::ActiveColor,
+                offset,
+                FenErrorKind::InvalidActiveColor,
+            ))
+        }
+    };
+
+    parse_castling(&mut board, fields[2], mode)?;
+    parse_en_passant(&mut board, fields[3], mode)?;
+
+    // Move counters
+    if let Some(&(offset, clock)) = fields.get(4) {
+        board.halfmove_clock = clock.parse().map_err(|_| {
+            FenError::new(FenField::HalfmoveClock, offset, FenErrorKind::InvalidNumber)
+        })?;
+    }
+    if let Some(&(offset, number)) = fields.get(5) {
+        board.fullmove_number = number.parse().map_err(|_| {
+            FenError::new(
+                FenField::FullmoveNumber,
+                offset,
+                FenErrorKind::InvalidNumber,
+            )
+        })?;
+        if board.fullmove_number == 0 {
+            if mode == FenMode::Strict {
+                return Err(FenError::new(
+                    FenField::FullmoveNumber,
+                    offset,
+                    FenErrorKind::InvalidNumber,
+                ));
+            }
+            board.fullmove_number = 1;
+        }
+    }
+
+    if mode != FenMode::Basic {
+        validate_position(&board, fields[0].0)?;
+    }
+    board.zobrist = board.compute_zobrist_hash();
+    Ok(board)
+}
+
+/// Splits a FEN record on whitespace, keeping the offset of each field
+fn split_fields(fen: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+    for (i, c) in fen.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                fields.push((s, &fen[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        fields.push((s, &fen[s..]));
+    }
+    fields
+}
+
+/// Parses the piece placement field into the board
+fn parse_placement(board: &mut Board, (start, placement): (usize, &str)) -> Result<(), FenError> {
+    let error = |offset: usize, kind| FenError::new(FenField::PiecePlacement, start + offset, kind);
+    let mut rank = 8;
+    let mut file = 1;
+    for (i, c) in placement.char_indices() {
+        match c {
+            '/' => {
+                if file <= 8 {
+                    return Err(error(i, FenErrorKind::RankTooShort));
+                }
+                if rank == 1 {
+                    return Err(error(i, FenErrorKind::WrongRankCount));
+                }
+                rank -= 1;
+                file = 1;
+            }
+            '1'..='8' => {
+                file += c.to_digit(10).unwrap() as i8;
+                if file > 9 {
+                    return Err(error(i, FenErrorKind::RankTooLong));
+                }
+            }
+            _ => {
+                let piece_type = match c.to_ascii_lowercase() {
+                    'p' => PieceType::Pawn,
+                    'n' => PieceType::Knight,
+                    'b' => PieceType::Bishop,
+                    'r' => PieceType::Rook,
+                    'q' => PieceType::Queen,
+                    'k' => PieceType::King,
+                    _ => return Err(error(i, FenErrorKind::InvalidCharacter(c))),
+                };
+                if file > 8 {
+                    return Err(error(i, FenErrorKind::RankTooLong));
+                }
+                let color = if c.is_ascii_uppercase() {
+                    Color::White
+                } else {
+                    Color::Black
+                };
+                board.set(
+                    Coordinates { x: file, y: rank },
+                    Some(Piece {
+                        piece_type,
+                        color,
+                        has_moved: false,
+                    }),
+                );
+                file += 1;
+            }
+        }
+    }
+    if file <= 8 {
+        return Err(error(placement.len(), FenErrorKind::RankTooShort));
+    }
+    if rank != 1 {
+        return Err(error(placement.len(), FenErrorKind::WrongRankCount));
+    }
+    Ok(())
+}
+
+/// Parses the castling rights field in standard, X-FEN or Shredder-FEN form, checking each right
+/// against the king and rook positions.
+///
+/// `K` and `Q` name the outermost rook on that side of the king; a file letter names the rook directly.
+fn parse_castling(
+    board: &mut Board,
+    (start, castling): (usize, &str),
+    mode: FenMode,
+) -> Result<(), FenError> {
+    let error = |offset: usize, kind| FenError::new(FenField::CastlingRights, start + offset, kind);
+    board.castling_rights = [None; 4];
+    if castling == "-" {
+        return Ok(());
+    }
+    for (i, c) in castling.char_indices() {
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let home_rank = if color == Color::White { 1 } else { 8 };
+        let has_piece = |x: i8, piece_type: PieceType| {
+            board
+                .get(Coordinates::new(x, home_rank))
+                .is_some_and(|p| p.piece_type == piece_type && p.color == color)
+        };
+        let king_file = (1..=8).find(|&x| has_piece(x, PieceType::King));
+        let (kingside, rook_file) = match c.to_ascii_lowercase() {
+            'k' => (true, outermost_rook(board, color, true)),
+            'q' => (false, outermost_rook(board, color, false)),
+            'a'..='h' => {
+                let file = (c.to_ascii_lowercase() as u8 - b'a' + 1) as i8;
+                (file > king_file.unwrap_or(5), Some(file))
+            }
+            _ => return Err(error(i, FenErrorKind::InvalidCharacter(c))),
+        };
+        let index = match (color, kingside) {
+            (Color::White, true) => 0,
+            (Color::White, false) => 1,
+            (Color::Black, true) => 2,
+            (Color::Black, false) => 3,
+        };
+        if board.castling_rights[index].is_some() && mode == FenMode::Strict {
+            return Err(error(i, FenErrorKind::DuplicateCastlingRight));
+        }
+        let consistent =
+            king_file.is_some() && rook_file.is_some_and(|x| has_piece(x, PieceType::Rook));
+        match mode {
+            FenMode::Strict if !consistent => {
+                return Err(error(i, FenErrorKind::InvalidCastlingRight));
+            }
+            FenMode::Lenient if !consistent => {}
+            _ => {
+                let standard = if kingside { 8 } else { 1 };
+                board.castling_rights[index] = Some(rook_file.unwrap_or(standard));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Finds the rook of `color` nearest the board edge on one side of its king on the back rank
+fn outermost_rook(board: &Board, color: Color, kingside: bool) -> Option<i8> {
+    let rank = if color == Color::White { 1 } else { 8 };
+    let files: Vec<i8> = if kingside {
+        (1..=8).rev().collect()
+    } else {
+        (1..=8).collect()
+    };
+    for x in files {
+        match board.get(Coordinates::new(x, rank)) {
+            Some(p) if p.color == color && p.piece_type == PieceType::Rook => return Some(x),
+            Some(p) if p.color == color && p.piece_type == PieceType::King => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Writes the castling rights field, as X-FEN or as Shredder-FEN with a file letter for every right.
+///
+/// X-FEN uses `KQkq` where that names the right rook, which covers every standard chess position.
+pub(crate) fn castling_field(board: &Board, shredder: bool) -> String {
+    let mut field = String::new();
+    for (index, right) in board.castling_rights.iter().enumerate() {
+        let file = match right {
+            Some(file) => *file,
+            None => continue,
+        };
+        let color = if index < 2 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let kingside = index % 2 == 0;
+        let standard = if kingside { 8 } else { 1 };
+        let c = if !shredder && outermost_rook(board, color, kingside).unwrap_or(standard) == file {
+            if kingside {
+                'k'
+            } else {
+                'q'
+            }
+        } else {
+            (b'a' + (file - 1) as u8) as char
+        };
+        field.push(match color {
+            Color::White => c.to_ascii_uppercase(),
+            Color::Black => c,
+        });
+    }
+    if field.is_empty() {
+        field.push('-');
+    }
+    field
+}
+
+/// Parses the en passant field, checking that a double pawn push could have produced it
+fn parse_en_passant(
+    board: &mut Board,
+    (start, en_passant): (usize, &str),
+    mode: FenMode,
+) -> Result<(), FenError> {
+    if en_passant == "-" {
+        return Ok(());
+    }
+    let square = Coordinates::from_notation_string(en_passant)
+        .ok()
+        .filter(|_| en_passant.starts_with(|c: char| c.is_ascii_lowercase()))
+        .ok_or(FenError::new(
+            FenField::EnPassant,
+            start,
+            FenErrorKind::InvalidEnPassantSquare,
+        ))?;
+    if mode != FenMode::Basic && !is_possible_en_passant(board, square) {
+        if mode == FenMode::Strict {
+            return Err(FenError::new(
+                FenField::EnPassant,
+                start,
+                FenErrorKind::ImpossibleEnPassant,
+            ));
+        }
+        return Ok(());
+    }
+    board.en_passant = Some(square);
+    Ok(())
+}
+
+/// Checks that an opponent pawn just passed over `square` with a double push
+fn is_possible_en_passant(board: &Board, square: Coordinates) -> bool {
+    let (ep_rank, dir) = match board.color_to_move {
+        Color::White => (6, -1),
+        Color::Black => (3, 1),
+    };
+    let pawn_color = board.color_to_move.reverse();
+    square.y == ep_rank
+        && board.get(square).is_none()
+        && board
+            .get(Coordinates::new(square.x, square.y - dir))
+            .is_none()
+        && board
+            .get(Coordinates::new(square.x, square.y + dir))
+            .is_some_and(|p| p.piece_type == PieceType::Pawn && p.color == pawn_color)
+}
+
+/// Checks king counts, pawn ranks and that the side that just moved is not in check
+fn validate_position(board: &Board, placement_offset: usize) -> Result<(), FenError> {
+    let error = |kind| FenError::new(FenField::PiecePlacement, placement_offset, kind);
+    for color in [Color::White, Color::Black] {
+        let kings = board
+            .squares
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|p| p.piece_type == PieceType::King && p.color == color)
+            .count();
+        if kings != 1 {
+            return Err(error(FenErrorKind::InvalidKingCount(color)));
+        }
+    }
+    let back_ranks = [board.squares[0], board.squares[7]];
+    if back_ranks
+        .iter()
+        .flatten()
+        .flatten()
+        .any(|p| p.piece_type == PieceType::Pawn)
+    {
+        return Err(error(FenErrorKind::PawnOnBackRank));
+    }
+    let mut opponent = board.clone();
+    opponent.color_to_move = board.color_to_move.reverse();
+    if opponent.is_in_check() {
+        return Err(error(FenErrorKind::OpponentInCheck));
+    }
+    Ok(())
+}
@@ -0,0 +1,272 @@
+use crate::chess::board::board::{Board, Piece};
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::board::moves::Move;
+use crate::chess::piece::piece::{Color, PieceType};
+
+impl Board {
+    /// Generate all legal moves for the current player with the original square-by-square generator.
+    ///
+    /// Kept as a reference for testing the bitboard generator behind `generate_moves`.
+    pub fn generate_moves_mailbox(&self) -> Vec<Move> {
+        let mut legal_moves = Vec::new();
+        for y in 1..=8 {
+            for x in 1..=8 {
+                let from = Coordinates { x, y };
+                if let Some(piece) = self.get(from) {
+                    if piece.color == self.color_to_move {
+                        for to in self.generate_piece_moves(from, piece) {
+                            // Check if this move would capture the enemy king (ILLEGAL)
+                            if let Some(target_piece) = self.get(to) {
+                                if target_piece.piece_type == PieceType::King {
+                                    continue; // Skip moves that capture the king
+                                }
+                            }
+                            for mv in self.build_moves(from, to, piece) {
+                                // Check if our own king is still safe after the move
+                                let mut clone = self.clone();
+                                clone.execute_move(&mv);
+                                if !clone.is_color_in_check_mailbox(piece.color) {
+                                    legal_moves.push(mv);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        legal_moves
+    }
+
+    /// Check if the king of the given color is attacked.
+    fn is_color_in_check_mailbox(&self, color: Color) -> bool {
+        let king_pos = match self.find_king(color) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        let opponent = color.reverse();
+        for row in 0..8 {
+            for col in 0..8 {
+                if let Some(piece) = self.squares[row][col] {
+                    if piece.color == opponent {
+                        let from = Coordinates {
+                            x: (col + 1) as i8,
+                            y: (row + 1) as i8,
+                        };
+                        let moves = self.generate_piece_moves(from, piece);
+                        if moves.contains(&king_pos) {
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Generate all moves for a piece at a given position (ignores check).
+    fn generate_piece_moves(&self, from: Coordinates, piece: Piece) -> Vec<Coordinates> {
+        let mut moves = Vec::new();
+        match piece.piece_type {
+            PieceType::Pawn => {
+                let dir = if piece.color == Color::White { 1 } else { -1 };
+                let next_y = from.y + dir;
+                if (1..=8).contains(&next_y) {
+                    // Forward
+                    let forward = Coordinates {
+                        x: from.x,
+                        y: next_y,
+                    };
+                    if self.get(forward).is_none() {
+                        moves.push(forward);
+                        // Double move
+                        let start_row = if piece.color == Color::White { 2 } else { 7 };
+                        if from.y == start_row {
+                            let double_forward = Coordinates {
+                                x: from.x,
+                                y: from.y + 2 * dir,
+                            };
+                            if self.get(double_forward).is_none() {
+                                moves.push(double_forward);
+                            }
+                        }
+                    }
+                    // Captures
+                    for dx in [-1, 1].iter() {
+                        let nx = from.x + dx;
+                        if (1..=8).contains(&nx) {
+                            let capture = Coordinates { x: nx, y: next_y };
+                            if let Some(target) = self.get(capture) {
+                                if target.color != piece.color {
+                                    moves.push(capture);
+                                }
+                            } else if let Some(ep) = self.en_passant {
+                                if capture == ep {
+                                    moves.push(capture);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            PieceType::Knight => {
+                let knight_moves = [
+                    (2, 1),
+                    (1, 2),
+                    (-1, 2),
+                    (-2, 1),
+                    (-2, -1),
+                    (-1, -2),
+                    (1, -2),
+                    (2, -1),
+                ];
+                for (dx, dy) in knight_moves.iter() {
+                    let nx = from.x + dx;
+                    let ny = from.y + dy;
+                    if (1..=8).contains(&nx) && (1..=8).contains(&ny) {
+                        let to = Coordinates { x: nx, y: ny };
+                        if let Some(target) = self.get(to) {
+                            if target.color != piece.color {
+                                moves.push(to);
+                            }
+                        } else {
+                            moves.push(to);
+                        }
+                    }
+                }
+            }
+            PieceType::Bishop => {
+                let directions = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+                for (dx, dy) in directions.iter() {
+                    let mut nx = from.x + dx;
+                    let mut ny = from.y + dy;
+                    while (1..=8).contains(&nx) && (1..=8).contains(&ny) {
+                        let to = Coordinates { x: nx, y: ny };
+                        if let Some(target) = self.get(to) {
+                            if target.color != piece.color {
+                                moves.push(to);
+                            }
+                            break;
+                        } else {
+                            moves.push(to);
+                        }
+                        nx += dx;
+                        ny += dy;
+                    }
+                }
+            }
+            PieceType::Rook => {
+                let directions = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+                for (dx, dy) in directions.iter() {
+                    let mut nx = from.x + dx;
+                    let mut ny = from.y + dy;
+                    while (1..=8).contains(&nx) && (1..=8).contains(&ny) {
+                        let to = Coordinates { x: nx, y: ny };
+                        if let Some(target) = self.get(to) {
+                            if target.color != piece.color {
+                                moves.push(to);
+                            }
+                            break;
+                        } else {
+                            moves.push(to);
+                        }
+                        nx += dx;
+                        ny += dy;
+                    }
+                }
+            }
+            PieceType::Queen => {
+                let directions = [
+                    (1, 1),
+                    (1, -1),
+                    (-1, 1),
+                    (-1, -1),
+                    (1, 0),
+                    (-1, 0),
+                    (0, 1),
+                    (0, -1),
+                ];
+                for (dx, dy) in directions.iter() {
+                    let mut nx = from.x + dx;
+                    let mut ny = from.y + dy;
+                    while (1..=8).contains(&nx) && (1..=8).contains(&ny) {
+                        let to = Coordinates { x: nx, y: ny };
+                        if let Some(target) = self.get(to) {
+                            if target.color != piece.color {
+                                moves.push(to);
+                            }
+                            break;
+                        } else {
+                            moves.push(to);
+                        }
+                        nx += dx;
+                        ny += dy;
+                    }
+                }
+            }
+            PieceType::King => {
+                let king_moves = [
+                    (1, 0),
+                    (1, 1),
+                    (0, 1),
+                    (-1, 1),
+                    (-1, 0),
+                    (-1, -1),
+                    (0, -1),
+                    (1, -1),
+                ];
+                for (dx, dy) in king_moves.iter() {
+                    let nx = from.x + dx;
+                    let ny = from.y + dy;
+                    if (1..=8).contains(&nx) && (1..=8).contains(&ny) {
+                        let to = Coordinates { x: nx, y: ny };
+                        if let Some(target) = self.get(to) {
+                            if target.color != piece.color {
+                                moves.push(to);
+                            }
+                        } else {
+                            moves.push(to);
+                        }
+                    }
+                }
+                // Castling
+                // Standard chess only: king on the e-file and rooks on the a- and h-files
+                let (rank, kingside, queenside) = match piece.color {
+                    Color::White => (1, self.castling_rights[0], self.castling_rights[1]),
+                    Color::Black => (8, self.castling_rights[2], self.castling_rights[3]),
+                };
+                let kingside = from.x == 5 && kingside == Some(8);
+                let queenside = from.x == 5 && queenside == Some(1);
+                let is_attacked =
+                    |sq: Coordinates| self.is_square_attacked(sq, piece.color.reverse());
+                // Kingside
+                if kingside
+                    && self.get(Coordinates { x: 6, y: rank }).is_none()
+                    && self.get(Coordinates { x: 7, y: rank }).is_none()
+                    && self
+                        .get(Coordinates { x: 8, y: rank })
+                        .is_some_and(|r| r.piece_type == PieceType::Rook && r.color == piece.color)
+                    && !is_attacked(Coordinates { x: 5, y: rank })
+                    && !is_attacked(Coordinates { x: 6, y: rank })
+                    && !is_attacked(Coordinates { x: 7, y: rank })
+                {
+                    moves.push(Coordinates { x: 7, y: rank });
+                }
+                // Queenside
+                if queenside
+                    && self.get(Coordinates { x: 4, y: rank }).is_none()
+                    && self.get(Coordinates { x: 3, y: rank }).is_none()
+                    && self.get(Coordinates { x: 2, y: rank }).is_none()
+                    && self
+                        .get(Coordinates { x: 1, y: rank })
+                        .is_some_and(|r| r.piece_type == PieceType::Rook && r.color == piece.color)
+                    && !is_attacked(Coordinates { x: 5, y: rank })
+                    && !is_attacked(Coordinates { x: 4, y: rank })
+                    && !is_attacked(Coordinates { x: 3, y: rank })
+                {
+                    moves.push(Coordinates { x: 3, y: rank });
+                }
+            }
+        }
+        moves
+    }
+}
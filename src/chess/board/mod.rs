@@ -1,4 +1,15 @@
+pub mod bitboard;
+#[allow(clippy::module_inception)]
 pub mod board;
+mod chess960;
 pub mod coordinates;
+pub mod fen;
+mod mailbox;
+mod movegen;
+pub mod moves;
 pub mod notation;
-
+pub mod perft;
+pub mod pins;
+pub mod san;
+pub mod uci;
+mod zobrist;
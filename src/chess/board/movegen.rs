@@ -0,0 +1,169 @@
+use crate::chess::board::bitboard::{
+    between, bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks,
+    rook_attacks, square_bit, squares,
+};
+use crate::chess::board::board::{castling_rook_squares, Board};
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::board::moves::{Move, MoveKind};
+use crate::chess::piece::piece::{Color, PieceType};
+
+/// Generates the legal moves of the side to move from the board's bitboards
+pub(crate) fn legal_moves(board: &Board) -> Vec<Move> {
+    let bitboards = board.bitboards();
+    let us = board.color_to_move;
+    let own = bitboards.color(us);
+    let occupied = bitboards.occupied();
+    // Moves onto the enemy king are never generated
+    let enemy_king = bitboards.pieces_of(us.reverse(), PieceType::King);
+    let mut moves = Vec::new();
+    for from in squares(own) {
+        let from_coord = Coordinates::from_index(from);
+        let piece = board
+            .get(from_coord)
+            .expect("bitboards match the squares array");
+        let targets = match piece.piece_type {
+            PieceType::Pawn => pawn_targets(board, from, us),
+            PieceType::Knight => knight_attacks(from),
+            PieceType::Bishop => bishop_attacks(from, occupied),
+            PieceType::Rook => rook_attacks(from, occupied),
+            PieceType::Queen => queen_attacks(from, occupied),
+            PieceType::King => king_attacks(from),
+        };
+        for to in squares(targets & !own & !enemy_king) {
+            for mv in board.build_moves(from_coord, Coordinates::from_index(to), piece) {
+                if !leaves_king_attacked(board, &mv, us) {
+                    moves.push(mv);
+                }
+            }
+        }
+        if piece.piece_type == PieceType::King {
+            castling_moves(board, from, us, &mut moves);
+        }
+    }
+    moves
+}
+
+/// Gets the pushes and captures, including en passant, of a pawn
+fn pawn_targets(board: &Board, from: usize, color: Color) -> u64 {
+    let bitboards = board.bitboards();
+    let occupied = bitboards.occupied();
+    let rank = from / 8;
+    let (last_rank, start_rank) = match color {
+        Color::White => (7, 1),
+        Color::Black => (0, 6),
+    };
+    if rank == last_rank {
+        return 0;
+    }
+    let forward = |square: usize| match color {
+        Color::White => square + 8,
+        Color::Black => square - 8,
+    };
+
+    let mut targets = 0;
+    let single = forward(from);
+    if occupied & square_bit(single) == 0 {
+        targets |= square_bit(single);
+        if rank == start_rank && occupied & square_bit(forward(single)) == 0 {
+            targets |= square_bit(forward(single));
+        }
+    }
+    let mut capturable = bitboards.color(color.reverse());
+    if let Some(en_passant) = board.en_passant.filter(Coordinates::is_valid) {
+        let square = en_passant.to_index().expect("valid coordinates");
+        if occupied & square_bit(square) == 0 {
+            capturable |= square_bit(square);
+        }
+    }
+    targets | (pawn_attacks(color, from) & capturable)
+}
+
+/// Adds the legal castling moves of a king on its back rank, following the Chess960 rules.
+///
+/// The king ends on the g- or c-file and the rook next to it on the f- or d-file; every square
+/// either piece crosses must be empty apart from the two of them, and the king may not start on,
+/// cross or land on an attacked square. Standard castling is the case of a king on e1 or e8.
+fn castling_moves(board: &Board, from: usize, color: Color, moves: &mut Vec<Move>) {
+    let rank = match color {
+        Color::White => 0,
+        Color::Black => 7,
+    };
+    if from / 8 != rank {
+        return;
+    }
+    let bitboards = board.bitboards();
+    let rooks = bitboards.pieces_of(color, PieceType::Rook);
+    let span = |a: usize, b: usize| between(a, b) | square_bit(a) | square_bit(b);
+    let is_attacked =
+        |square: usize| board.is_square_attacked(Coordinates::from_index(square), color.reverse());
+
+    for (kind, king_file) in [
+        (MoveKind::KingsideCastle, 6),
+        (MoveKind::QueensideCastle, 2),
+    ] {
+        let king_to = rank * 8 + king_file;
+        let mut mv = Move::new(
+            Coordinates::from_index(from),
+            Coordinates::from_index(king_to),
+        );
+        mv.kind = kind;
+        let (rook_from, rook_to) = match castling_rook_squares(&board.castling_rights, &mv) {
+            Some((rook_from, rook_to)) => (
+                rook_from.to_index().expect("valid coordinates"),
+                rook_to.to_index().expect("valid coordinates"),
+            ),
+            None => continue,
+        };
+        if rooks & square_bit(rook_from) == 0
+            || (kind == MoveKind::KingsideCastle) != (rook_from > from)
+        {
+            continue;
+        }
+        let crossed = span(from, king_to) | span(rook_from, rook_to);
+        if crossed & bitboards.occupied() & !square_bit(from) & !square_bit(rook_from) != 0 {
+            continue;
+        }
+        if squares(span(from, king_to)).any(is_attacked) {
+            continue;
+        }
+        if !leaves_king_attacked(board, &mv, color) {
+            moves.push(mv);
+        }
+    }
+}
+
+/// Checks if a pseudo-legal move would leave the mover's king attacked
+fn leaves_king_attacked(board: &Board, mv: &Move, color: Color) -> bool {
+    let bitboards = board.bitboards();
+    let from = square_bit(mv.from.to_index().expect("valid coordinates"));
+    let to = square_bit(mv.to.to_index().expect("valid coordinates"));
+    let captured = match mv.kind {
+        MoveKind::EnPassant => square_bit(
+            Coordinates::new(mv.to.x, mv.from.y)
+                .to_index()
+                .expect("valid coordinates"),
+        ),
+        MoveKind::KingsideCastle | MoveKind::QueensideCastle => 0,
+        _ => to,
+    };
+    // The castling rook moves too, which may open or close lines to the king
+    let (rook_from, rook_to) = match castling_rook_squares(&board.castling_rights, mv) {
+        Some((rook_from, rook_to)) => (
+            square_bit(rook_from.to_index().expect("valid coordinates")),
+            square_bit(rook_to.to_index().expect("valid coordinates")),
+        ),
+        None => (0, 0),
+    };
+    let occupied = (bitboards.occupied() & !from & !captured & !rook_from) | to | rook_to;
+
+    // The first king in square order, as `find_king` would report after the move
+    let mut kings = bitboards.pieces_of(color, PieceType::King);
+    if kings & from != 0 {
+        kings = (kings & !from) | to;
+    }
+    let king = match squares(kings).next() {
+        Some(king) => king,
+        None => return false,
+    };
+    bitboards.attackers_to(king, color.reverse(), occupied) & !captured != 0
+}
@@ -0,0 +1,111 @@
+use crate::chess::board::board::Piece;
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::piece::piece::PieceType;
+
+/// Special move categories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveKind {
+    /// Regular move or capture
+    Normal,
+    /// Pawn advancing two squares from its starting rank
+    DoublePawnPush,
+    /// Pawn capturing en passant
+    EnPassant,
+    /// Castling towards the h-file
+    KingsideCastle,
+    /// Castling towards the a-file
+    QueensideCastle,
+}
+
+/// A single chess move with promotion, capture and special-move information
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move {
+    /// Square the piece moves from
+    pub from: Coordinates,
+    /// Square the piece moves to (the king's destination when castling)
+    pub to: Coordinates,
+    /// Piece a pawn promotes to
+    pub promotion: Option<PieceType>,
+    /// Piece type removed from the board by this move
+    pub captured: Option<PieceType>,
+    /// Special move flag
+    pub kind: MoveKind,
+}
+
+impl Move {
+    /// Creates a quiet move without special flags
+    pub fn new(from: Coordinates, to: Coordinates) -> Move {
+        Move {
+            from,
+            to,
+            promotion: None,
+            captured: None,
+            kind: MoveKind::Normal,
+        }
+    }
+
+    /// Creates a move promoting to the given piece
+    pub fn with_promotion(from: Coordinates, to: Coordinates, promotion: PieceType) -> Move {
+        Move {
+            promotion: Some(promotion),
+            ..Move::new(from, to)
+        }
+    }
+
+    /// Checks if the move removes an opponent piece
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+
+    /// Checks if the move promotes a pawn
+    pub fn is_promotion(&self) -> bool {
+        self.promotion.is_some()
+    }
+
+    /// Checks if the move is kingside or queenside castling
+    pub fn is_castle(&self) -> bool {
+        matches!(
+            self.kind,
+            MoveKind::KingsideCastle | MoveKind::QueensideCastle
+        )
+    }
+
+    /// Checks if the move is an en passant capture
+    pub fn is_en_passant(&self) -> bool {
+        self.kind == MoveKind::EnPassant
+    }
+
+    /// Converts to a (from, to) tuple
+    pub fn to_tuple(&self) -> (Coordinates, Coordinates) {
+        (self.from, self.to)
+    }
+}
+
+impl From<Move> for (Coordinates, Coordinates) {
+    fn from(mv: Move) -> Self {
+        mv.to_tuple()
+    }
+}
+
+/// State needed to take back a move made with `Board::make_move`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undo {
+    /// The move as it was executed
+    pub mv: Move,
+    /// The moving piece before the move
+    pub moved: Piece,
+    /// The piece removed from the board, if any
+    pub captured: Option<Piece>,
+    /// The castling rook before the move
+    pub rook: Option<Piece>,
+    /// Castling rights before the move
+    pub castling_rights: [Option<i8>; 4],
+    /// En passant square before the move
+    pub en_passant: Option<Coordinates>,
+    /// Halfmove clock before the move
+    pub halfmove_clock: u32,
+    /// Fullmove number before the move
+    pub fullmove_number: u32,
+    /// Zobrist hash before the move
+    pub zobrist_hash: u64,
+}
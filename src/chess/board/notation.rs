@@ -1,6 +1,7 @@
 use std::convert::TryInto;
 
 use super::coordinates::Coordinates;
+use crate::chess::error::ChessError;
 
 /// Algebraic chess notation (e.g., "e4", "a1")
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,24 +15,24 @@ pub struct Notation {
 
 impl Notation {
     /// Creates notation from file and rank
-    pub fn new(file: char, rank: char) -> Result<Notation, &'static str> {
+    pub fn new(file: char, rank: char) -> Result<Notation, ChessError> {
         if !file.is_ascii_lowercase() {
-            return Err("File should be a lowercase ASCII letter");
+            return Err(ChessError::InvalidNotation(format!("{}{}", file, rank)));
         }
-        if !rank.is_digit(10) {
-            return Err("Rank should be a digit");
+        if !rank.is_ascii_digit() {
+            return Err(ChessError::InvalidNotation(format!("{}{}", file, rank)));
         }
         Ok(Notation { file, rank })
     }
 
     /// Converts coordinate string to notation
-    pub fn from_coordinates_string(input: &str) -> Result<Notation, &'static str> {
+    pub fn from_coordinates_string(input: &str) -> Result<Notation, ChessError> {
         let coordinates = Coordinates::from_string(input)?;
         Notation::from_coordinates(coordinates)
     }
 
     /// Converts coordinates to algebraic notation
-    pub fn from_coordinates(coordinates: Coordinates) -> Result<Notation, &'static str> {
+    pub fn from_coordinates(coordinates: Coordinates) -> Result<Notation, ChessError> {
         let file = match coordinates.x {
             1 => 'a',
             2 => 'b',
@@ -41,7 +42,12 @@ impl Notation {
             6 => 'f',
             7 => 'g',
             8 => 'h',
-            _ => return Err("Invalid x coordinate for notation conversion"),
+            _ => {
+                return Err(ChessError::InvalidCoordinates(format!(
+                    "{},{}",
+                    coordinates.x, coordinates.y
+                )))
+            }
         };
         let rank = match coordinates.y {
             1 => '1',
@@ -52,20 +58,35 @@ impl Notation {
             6 => '6',
             7 => '7',
             8 => '8',
-            _ => return Err("Invalid y coordinate for notation conversion"),
+            _ => {
+                return Err(ChessError::InvalidCoordinates(format!(
+                    "{},{}",
+                    coordinates.x, coordinates.y
+                )))
+            }
         };
         Ok(Notation { file, rank })
     }
 
     /// Converts the `Notation` instance to a `Coordinates` instance.
-    pub fn to_coordinates(&self) -> Result<Coordinates, &'static str> {
+    pub fn to_coordinates(&self) -> Result<Coordinates, ChessError> {
         let x = match self.file {
-            'a'..='h' => (self.file as u8 - 'a' as u8 + 1) as usize,
-            _ => return Err("Invalid file for coordinate conversion"),
+            'a'..='h' => (self.file as u8 - b'a' + 1) as usize,
+            _ => {
+                return Err(ChessError::InvalidNotation(format!(
+                    "{}{}",
+                    self.file, self.rank
+                )))
+            }
         };
         let y = match self.rank {
-            '1'..='8' => (self.rank as u8 - '1' as u8 + 1) as usize,
-            _ => return Err("Invalid rank for coordinate conversion"),
+            '1'..='8' => (self.rank as u8 - b'1' + 1) as usize,
+            _ => {
+                return Err(ChessError::InvalidNotation(format!(
+                    "{}{}",
+                    self.file, self.rank
+                )))
+            }
         };
         Ok(Coordinates {
             x: x.try_into()
@@ -76,11 +97,11 @@ impl Notation {
     }
 
     /// Converts the `Notation` instance to a string representation.
-    pub fn from_string(input: &str) -> Result<Notation, &'static str> {
+    pub fn from_string(input: &str) -> Result<Notation, ChessError> {
         let chars: Vec<char> = input.chars().collect();
         if chars.len() != 2 {
-            return Err("Input should be exactly two characters");
+            return Err(ChessError::InvalidNotation(input.to_string()));
         }
-        Notation::new(chars[0], chars[1]).map_err(|_| "Invalid notation")
+        Notation::new(chars[0], chars[1])
     }
 }
@@ -0,0 +1,49 @@
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+
+impl Board {
+    /// Counts the leaf nodes of the legal move tree to the given depth
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let mut board = self.clone();
+        perft_nodes(&mut board, depth)
+    }
+
+    /// Counts the leaf nodes below each legal root move, in move generation order
+    pub fn perft_divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+        let mut board = self.clone();
+        board
+            .generate_moves()
+            .into_iter()
+            .map(|mv| {
+                let undo = board.execute_move(&mv);
+                let nodes = perft_nodes(&mut board, depth - 1);
+                board.unmake_move(undo);
+                (mv, nodes)
+            })
+            .collect()
+    }
+}
+
+/// Walks the move tree with make/unmake, counting the moves at the last ply in bulk
+fn perft_nodes(board: &mut Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let moves = board.generate_moves();
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    let mut nodes = 0;
+    for mv in moves {
+        let undo = board.execute_move(&mv);
+        nodes += perft_nodes(board, depth - 1);
+        board.unmake_move(undo);
+    }
+    nodes
+}
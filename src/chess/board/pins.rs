@@ -0,0 +1,96 @@
+use crate::chess::board::bitboard::{between, bishop_attacks, rook_attacks, square_bit, squares};
+use crate::chess::board::board::{castling_rook_squares, Board};
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::board::moves::{Move, MoveKind};
+use crate::chess::piece::piece::{Color, PieceType};
+
+/// A piece that cannot leave the line between its king and an enemy slider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pin {
+    /// Square of the pinned piece
+    pub pinned: Coordinates,
+    /// Square of the bishop, rook or queen pinning it
+    pub pinner: Coordinates,
+    /// Squares from the king towards the pinner, ending on the pinner; the pinned piece may only move along them
+    pub ray: Vec<Coordinates>,
+}
+
+impl Board {
+    /// Gets the squares of the pieces giving check to the side to move
+    pub fn checkers(&self) -> Vec<Coordinates> {
+        match self.find_king(self.color_to_move) {
+            Some(king) => self.attackers_to(king, self.color_to_move.reverse()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Gets the pieces of `color` pinned against their own king, in a1 to h8 order of the pinners
+    pub fn pinned_pieces(&self, color: Color) -> Vec<Pin> {
+        let king = match self.find_king(color) {
+            Some(king) => king.to_index().expect("valid coordinates"),
+            None => return Vec::new(),
+        };
+        let bitboards = self.bitboards();
+        let enemy = color.reverse();
+        let queens = bitboards.pieces_of(enemy, PieceType::Queen);
+        // Enemy sliders that would attack the king on an empty board
+        let snipers = (bishop_attacks(king, 0)
+            & (bitboards.pieces_of(enemy, PieceType::Bishop) | queens))
+            | (rook_attacks(king, 0) & (bitboards.pieces_of(enemy, PieceType::Rook) | queens));
+
+        let mut pins = Vec::new();
+        for pinner in squares(snipers) {
+            let line = between(king, pinner);
+            let blockers = line & bitboards.occupied();
+            if blockers.count_ones() != 1 || blockers & bitboards.color(color) == 0 {
+                continue;
+            }
+            let mut ray: Vec<usize> = squares(line | square_bit(pinner)).collect();
+            if pinner < king {
+                ray.reverse();
+            }
+            pins.push(Pin {
+                pinned: Coordinates::from_index(blockers.trailing_zeros() as usize),
+                pinner: Coordinates::from_index(pinner),
+                ray: ray.into_iter().map(Coordinates::from_index).collect(),
+            });
+        }
+        pins
+    }
+
+    /// Checks if a move of the side to move uncovers a check by a piece other than the one moved
+    pub fn is_discovered_check(&self, mv: &Move) -> bool {
+        let mover = match self.get(mv.from) {
+            Some(piece) => piece.color,
+            None => return false,
+        };
+        let king = match self.find_king(mover.reverse()) {
+            Some(king) => king.to_index().expect("valid coordinates"),
+            None => return false,
+        };
+        let index = |coord: Coordinates| coord.to_index().expect("valid coordinates");
+        let from = square_bit(index(mv.from));
+        let to = square_bit(index(mv.to));
+        let captured = match mv.kind {
+            MoveKind::EnPassant => square_bit(index(Coordinates::new(mv.to.x, mv.from.y))),
+            MoveKind::KingsideCastle | MoveKind::QueensideCastle => 0,
+            _ => to,
+        };
+        let (rook_from, rook_to) = match castling_rook_squares(&self.castling_rights, mv) {
+            Some((rook_from, rook_to)) => {
+                (square_bit(index(rook_from)), square_bit(index(rook_to)))
+            }
+            None => (0, 0),
+        };
+
+        let bitboards = self.bitboards();
+        let occupied = (bitboards.occupied() & !from & !captured & !rook_from) | to | rook_to;
+        // Only pieces that stay where they are can give a discovered check
+        let stayed = bitboards.color(mover) & !from & !rook_from;
+        let queens = bitboards.pieces(PieceType::Queen);
+        let diagonal = (bitboards.pieces(PieceType::Bishop) | queens) & stayed;
+        let orthogonal = (bitboards.pieces(PieceType::Rook) | queens) & stayed;
+        (bishop_attacks(king, occupied) & diagonal) | (rook_attacks(king, occupied) & orthogonal)
+            != 0
+    }
+}
@@ -0,0 +1,185 @@
+use crate::chess::board::board::Board;
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::board::moves::{Move, MoveKind};
+use crate::chess::error::ChessError;
+use crate::chess::piece::piece::PieceType;
+
+impl Board {
+    /// Parses a move in Standard Algebraic Notation (e.g. "Nbd7", "exd5", "e8=Q", "O-O")
+    pub fn parse_san(&self, san: &str) -> Result<Move, ChessError> {
+        let written = san.trim();
+        let san = written.trim_end_matches(['+', '#', '!', '?']);
+        let invalid = || ChessError::InvalidSan(written.to_string());
+        let legal_moves = self.generate_moves();
+
+        // Castling
+        let castle_kind = match san {
+            "O-O" | "0-0" => Some(MoveKind::KingsideCastle),
+            "O-O-O" | "0-0-0" => Some(MoveKind::QueensideCastle),
+            _ => None,
+        };
+        if let Some(kind) = castle_kind {
+            return legal_moves
+                .into_iter()
+                .find(|mv| mv.kind == kind)
+                .ok_or_else(|| ChessError::IllegalMove(written.to_string()));
+        }
+
+        let mut chars: Vec<char> = san.chars().collect();
+        if chars.len() < 2 {
+            return Err(invalid());
+        }
+
+        // Moving piece
+        let piece_type = match piece_from_san_char(chars[0]) {
+            Some(piece_type) => {
+                chars.remove(0);
+                piece_type
+            }
+            None => PieceType::Pawn,
+        };
+
+        // Promotion suffix, with or without '='
+        let mut promotion = None;
+        if piece_type == PieceType::Pawn {
+            if let Some(&last) = chars.last() {
+                if let Some(promo) = piece_from_san_char(last.to_ascii_uppercase()) {
+                    if last.is_ascii_uppercase()
+                        || (chars.len() > 2 && chars[chars.len() - 2] == '=')
+                    {
+                        promotion = Some(promo);
+                        chars.pop();
+                        if chars.last() == Some(&'=') {
+                            chars.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        // Destination square
+        if chars.len() < 2 {
+            return Err(invalid());
+        }
+        let destination: String = chars[chars.len() - 2..].iter().collect();
+        let to = Coordinates::from_notation_string(&destination).map_err(|_| invalid())?;
+        chars.truncate(chars.len() - 2);
+
+        // Disambiguation and capture marker
+        let mut from_file = None;
+        let mut from_rank = None;
+        for c in chars {
+            match c {
+                'x' | ':' => {}
+                'a'..='h' => from_file = Some(c as i8 - 'a' as i8 + 1),
+                '1'..='8' => from_rank = Some(c as i8 - '1' as i8 + 1),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let mut candidates = legal_moves.into_iter().filter(|mv| {
+            mv.to == to
+                && self.get(mv.from).map(|p| p.piece_type) == Some(piece_type)
+                && from_file.is_none_or(|x| mv.from.x == x)
+                && from_rank.is_none_or(|y| mv.from.y == y)
+                && mv.promotion == promotion
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(mv), None) => Ok(mv),
+            (Some(_), Some(_)) => Err(ChessError::AmbiguousMove(written.to_string())),
+            (None, _) => Err(ChessError::IllegalMove(written.to_string())),
+        }
+    }
+
+    /// Formats a legal move in Standard Algebraic Notation, including check and mate suffixes
+    pub fn to_san(&self, mv: &Move) -> Result<String, ChessError> {
+        let mv = &self.resolve_move(mv)?;
+        let mut san = String::new();
+        let piece_type = self
+            .get(mv.from)
+            .map_or(PieceType::Pawn, |piece| piece.piece_type);
+
+        match mv.kind {
+            MoveKind::KingsideCastle => san.push_str("O-O"),
+            MoveKind::QueensideCastle => san.push_str("O-O-O"),
+            _ => {
+                if piece_type == PieceType::Pawn {
+                    if mv.is_capture() {
+                        san.push(mv.from.to_file());
+                    }
+                } else {
+                    san.push(san_char(piece_type));
+                    san.push_str(&self.disambiguation(mv, piece_type));
+                }
+                if mv.is_capture() {
+                    san.push('x');
+                }
+                san.push(mv.to.to_file());
+                san.push(mv.to.to_rank());
+                if let Some(promotion) = mv.promotion {
+                    san.push('=');
+                    san.push(san_char(promotion));
+                }
+            }
+        }
+
+        // Check and mate suffix
+        let mut after = self.clone();
+        after.execute_move(mv);
+        if after.is_in_check() {
+            san.push(if after.generate_moves().is_empty() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+        Ok(san)
+    }
+
+    /// Returns the file, rank or square needed to tell a move apart from other legal moves
+    fn disambiguation(&self, mv: &Move, piece_type: PieceType) -> String {
+        let others: Vec<Coordinates> = self
+            .generate_moves()
+            .into_iter()
+            .filter(|other| {
+                other.to == mv.to
+                    && other.from != mv.from
+                    && self.get(other.from).map(|p| p.piece_type) == Some(piece_type)
+            })
+            .map(|other| other.from)
+            .collect();
+        if others.is_empty() {
+            String::new()
+        } else if others.iter().all(|from| from.x != mv.from.x) {
+            mv.from.to_file().to_string()
+        } else if others.iter().all(|from| from.y != mv.from.y) {
+            mv.from.to_rank().to_string()
+        } else {
+            format!("{}{}", mv.from.to_file(), mv.from.to_rank())
+        }
+    }
+}
+
+/// Maps an uppercase SAN piece letter to its piece type
+fn piece_from_san_char(c: char) -> Option<PieceType> {
+    match c {
+        'N' => Some(PieceType::Knight),
+        'B' => Some(PieceType::Bishop),
+        'R' => Some(PieceType::Rook),
+        'Q' => Some(PieceType::Queen),
+        'K' => Some(PieceType::King),
+        _ => None,
+    }
+}
+
+/// Maps a piece type to its uppercase SAN letter
+fn san_char(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::Pawn => 'P',
+        PieceType::Knight => 'N',
+        PieceType::Bishop => 'B',
+        PieceType::Rook => 'R',
+        PieceType::Queen => 'Q',
+        PieceType::King => 'K',
+    }
+}
@@ -0,0 +1,90 @@
+use crate::chess::board::board::{castling_rook_squares, Board};
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::board::moves::{Move, MoveKind};
+use crate::chess::error::ChessError;
+use crate::chess::piece::piece::PieceType;
+
+impl Move {
+    /// Formats the move as a UCI long algebraic string (e.g. "e2e4", "e7e8q", "e1g1")
+    pub fn to_uci(&self) -> String {
+        let mut uci = format!(
+            "{}{}{}{}",
+            self.from.to_file(),
+            self.from.to_rank(),
+            self.to.to_file(),
+            self.to.to_rank()
+        );
+        if let Some(promotion) = self.promotion {
+            uci.push(match promotion {
+                PieceType::Knight => 'n',
+                PieceType::Bishop => 'b',
+                PieceType::Rook => 'r',
+                _ => 'q',
+            });
+        }
+        uci
+    }
+}
+
+impl Board {
+    /// Parses a UCI move string, accepting both "e1g1" and the king-takes-rook "e1h1" castling forms
+    pub fn parse_uci(&self, uci: &str) -> Result<Move, ChessError> {
+        let uci = uci.trim();
+        let invalid = || ChessError::InvalidUci(uci.to_string());
+        if !uci.is_ascii() || (uci.len() != 4 && uci.len() != 5) {
+            return Err(invalid());
+        }
+        let from = Coordinates::from_notation_string(&uci[0..2]).map_err(|_| invalid())?;
+        let to = Coordinates::from_notation_string(&uci[2..4]).map_err(|_| invalid())?;
+        let promotion = match uci.as_bytes().get(4) {
+            None => None,
+            Some(b'q') => Some(PieceType::Queen),
+            Some(b'r') => Some(PieceType::Rook),
+            Some(b'b') => Some(PieceType::Bishop),
+            Some(b'n') => Some(PieceType::Knight),
+            Some(b'k') => return Err(ChessError::InvalidPromotion(PieceType::King)),
+            Some(b'p') => return Err(ChessError::InvalidPromotion(PieceType::Pawn)),
+            Some(_) => return Err(invalid()),
+        };
+
+        // King onto its own rook is castling in Chess960 GUIs
+        if let (Some(king), Some(rook)) = (self.get(from), self.get(to)) {
+            if king.piece_type == PieceType::King
+                && rook.piece_type == PieceType::Rook
+                && king.color == rook.color
+            {
+                let kind = if to.x > from.x {
+                    MoveKind::KingsideCastle
+                } else {
+                    MoveKind::QueensideCastle
+                };
+                return self
+                    .generate_moves()
+                    .into_iter()
+                    .find(|mv| mv.from == from && mv.kind == kind)
+                    .ok_or_else(|| ChessError::IllegalMove(uci.to_string()));
+            }
+        }
+
+        let mv = self.find_legal_move(from, to, promotion)?;
+        if mv.is_promotion() && promotion.is_none() {
+            return Err(ChessError::MissingPromotion(uci.to_string()));
+        }
+        Ok(mv)
+    }
+
+    /// Formats a move in UCI notation using the king-takes-rook castling form of Chess960 GUIs
+    pub fn to_uci_chess960(&self, mv: &Move) -> String {
+        match castling_rook_squares(&self.castling_rights, mv) {
+            Some((rook_from, _)) => Move::new(mv.from, rook_from).to_uci(),
+            None => mv.to_uci(),
+        }
+    }
+
+    /// Parses and executes a UCI move string
+    pub fn apply_uci(&mut self, uci: &str) -> Result<Move, ChessError> {
+        let mv = self.parse_uci(uci)?;
+        self.execute_move(&mv);
+        Ok(mv)
+    }
+}
@@ -0,0 +1,474 @@
+use std::slice;
+use std::sync::OnceLock;
+
+use crate::chess::board::bitboard::{
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
+    square_bit, squares, Bitboards,
+};
+use crate::chess::board::board::Board;
+use crate::chess::error::ChessError;
+use crate::chess::piece::piece::{Color, PieceType};
+
+/// Piece types in weight index order
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+/// Names of the piece types in the weights text format
+const PIECE_NAMES: [&str; 6] = ["pawn", "knight", "bishop", "rook", "queen", "king"];
+
+/// Phase of a position with all minor and major pieces on the board
+const MAX_PHASE: i32 = 24;
+
+/// Squares of the a-file
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+
+/// A weight with separate middlegame and endgame values, blended by the game phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tapered {
+    /// Value with all pieces on the board
+    pub mg: i32,
+    /// Value with only kings and pawns left
+    pub eg: i32,
+}
+
+impl Tapered {
+    /// Creates a weight
+    pub const fn new(mg: i32, eg: i32) -> Tapered {
+        Tapered { mg, eg }
+    }
+
+    /// Adds `count` times `weight`
+    fn add(&mut self, weight: Tapered, count: i32) {
+        self.mg += weight.mg * count;
+        self.eg += weight.eg * count;
+    }
+}
+
+/// Terms of the static evaluation in centipawns, indexed by piece type from pawn to king where
+/// there is one per piece.
+///
+/// The weights can be saved with `to_text` and loaded with `parse`, one term per line:
+///
+/// ```text
+/// material.knight 320 300
+/// pst.pawn.rank2 5 0 10 0 10 0 -20 0 -20 0 10 0 10 0 5 0
+/// bishop_pair 30 50
+/// ```
+///
+/// Each middlegame value is followed by its endgame value. Piece-square tables are seen from White,
+/// one line per rank from a to h; Black uses them mirrored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalWeights {
+    /// Value of each piece
+    pub material: [Tapered; 6],
+    /// Bonus of each piece on each square, indexed by White's square from a1 to h8
+    pub psts: [[Tapered; 64]; 6],
+    /// Bonus per square a piece attacks that is neither its own nor covered by an enemy pawn
+    pub mobility: [Tapered; 6],
+    /// Penalty per pawn behind another on its file
+    pub doubled_pawn: Tapered,
+    /// Penalty per pawn without pawns of its color on the neighboring files
+    pub isolated_pawn: Tapered,
+    /// Bonus per pawn without enemy pawns ahead on its or the neighboring files, by rank from 1 to 8
+    pub passed_pawn: [Tapered; 8],
+    /// Bonus per pawn on the two ranks in front of its king, on the king's or a neighboring file
+    pub pawn_shield: Tapered,
+    /// Penalty per enemy attack on the king's square or the squares next to it
+    pub king_zone_attack: Tapered,
+    /// Bonus for having two bishops or more
+    pub bishop_pair: Tapered,
+}
+
+impl Default for EvalWeights {
+    fn default() -> Self {
+        let knight = pst(&KNIGHT_PST, &KNIGHT_PST);
+        let bishop = pst(&BISHOP_PST, &BISHOP_PST);
+        let rook = pst(&ROOK_PST, &ROOK_PST);
+        let queen = pst(&QUEEN_PST, &QUEEN_PST);
+        Self {
+            material: [
+                Tapered::new(100, 120),
+                Tapered::new(320, 300),
+                Tapered::new(330, 310),
+                Tapered::new(500, 520),
+                Tapered::new(900, 950),
+                Tapered::new(0, 0),
+            ],
+            psts: [
+                pst(&PAWN_MG_PST, &PAWN_EG_PST),
+                knight,
+                bishop,
+                rook,
+                queen,
+                pst(&KING_MG_PST, &KING_EG_PST),
+            ],
+            mobility: [
+                Tapered::new(0, 0),
+                Tapered::new(4, 4),
+                Tapered::new(5, 5),
+                Tapered::new(2, 4),
+                Tapered::new(1, 2),
+                Tapered::new(0, 0),
+            ],
+            doubled_pawn: Tapered::new(-10, -20),
+            isolated_pawn: Tapered::new(-10, -15),
+            passed_pawn: [
+                Tapered::new(0, 0),
+                Tapered::new(5, 10),
+                Tapered::new(10, 20),
+                Tapered::new(15, 35),
+                Tapered::new(25, 60),
+                Tapered::new(40, 100),
+                Tapered::new(60, 150),
+                Tapered::new(0, 0),
+            ],
+            pawn_shield: Tapered::new(10, 0),
+            king_zone_attack: Tapered::new(-6, -1),
+            bishop_pair: Tapered::new(30, 50),
+        }
+    }
+}
+
+impl EvalWeights {
+    /// Scores a position in centipawns from the side to move's point of view
+    pub fn evaluate(&self, board: &Board) -> i32 {
+        let bitboards = board.bitboards();
+        let mut score = self.side_score(bitboards, Color::White);
+        let black = self.side_score(bitboards, Color::Black);
+        score.mg -= black.mg;
+        score.eg -= black.eg;
+
+        let phase = [
+            (PieceType::Knight, 1),
+            (PieceType::Bishop, 1),
+            (PieceType::Rook, 2),
+            (PieceType::Queen, 4),
+        ]
+        .into_iter()
+        .map(|(piece_type, weight)| bitboards.pieces(piece_type).count_ones() as i32 * weight)
+        .sum::<i32>()
+        .min(MAX_PHASE);
+        let white = (score.mg * phase + score.eg * (MAX_PHASE - phase)) / MAX_PHASE;
+        match board.color_to_move {
+            Color::White => white,
+            Color::Black => -white,
+        }
+    }
+
+    /// Parses weights written by `to_text`; missing terms keep their default values, and blank lines
+    /// and lines starting with `#` are skipped
+    pub fn parse(text: &str) -> Result<EvalWeights, ChessError> {
+        let mut weights = EvalWeights::default();
+        let mut terms = weights.terms();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid =
+                |reason: &str| ChessError::InvalidEvalWeights(format!("{} in {}", reason, line));
+            let mut tokens = line.split_whitespace();
+            let name = tokens.next().expect("line is not empty");
+            let values = tokens
+                .map(|token| token.parse::<i32>().map_err(|_| invalid("invalid number")))
+                .collect::<Result<Vec<i32>, ChessError>>()?;
+            let (_, term) = terms
+                .iter_mut()
+                .find(|(term_name, _)| term_name == name)
+                .ok_or_else(|| invalid("unknown term"))?;
+            if values.len() != term.len() * 2 {
+                return Err(invalid("wrong number of values"));
+            }
+            for (weight, pair) in term.iter_mut().zip(values.chunks(2)) {
+                *weight = Tapered::new(pair[0], pair[1]);
+            }
+        }
+        drop(terms);
+        Ok(weights)
+    }
+
+    /// Writes the weights in the text format read by `parse`
+    pub fn to_text(&self) -> String {
+        let mut copy = self.clone();
+        let mut text = String::new();
+        for (name, term) in copy.terms() {
+            text.push_str(&name);
+            for weight in term.iter() {
+                text.push_str(&format!(" {} {}", weight.mg, weight.eg));
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Lists every term under its name in the text format
+    fn terms(&mut self) -> Vec<(String, &mut [Tapered])> {
+        let mut terms: Vec<(String, &mut [Tapered])> = Vec::new();
+        for (name, material) in PIECE_NAMES.iter().zip(self.material.iter_mut()) {
+            terms.push((format!("material.{}", name), slice::from_mut(material)));
+        }
+        for (name, pst) in PIECE_NAMES.iter().zip(self.psts.iter_mut()) {
+            for (rank, row) in pst.chunks_mut(8).enumerate() {
+                terms.push((format!("pst.{}.rank{}", name, rank + 1), row));
+            }
+        }
+        for (name, mobility) in PIECE_NAMES.iter().zip(self.mobility.iter_mut()) {
+            terms.push((format!("mobility.{}", name), slice::from_mut(mobility)));
+        }
+        terms.push((
+            "pawn.doubled".to_string(),
+            slice::from_mut(&mut self.doubled_pawn),
+        ));
+        terms.push((
+            "pawn.isolated".to_string(),
+            slice::from_mut(&mut self.isolated_pawn),
+        ));
+        terms.push(("pawn.passed".to_string(), &mut self.passed_pawn[..]));
+        terms.push((
+            "king.pawn_shield".to_string(),
+            slice::from_mut(&mut self.pawn_shield),
+        ));
+        terms.push((
+            "king.zone_attack".to_string(),
+            slice::from_mut(&mut self.king_zone_attack),
+        ));
+        terms.push((
+            "bishop_pair".to_string(),
+            slice::from_mut(&mut self.bishop_pair),
+        ));
+        terms
+    }
+
+    /// Sums the terms of one side
+    fn side_score(&self, bitboards: &Bitboards, color: Color) -> Tapered {
+        let mut score = Tapered::default();
+        let enemy = color.reverse();
+        let occupied = bitboards.occupied();
+        let own = bitboards.color(color);
+        let enemy_pawns = bitboards.pieces_of(enemy, PieceType::Pawn);
+        let pawn_covered =
+            squares(enemy_pawns).fold(0, |covered, square| covered | pawn_attacks(enemy, square));
+
+        for (index, piece_type) in PIECE_TYPES.into_iter().enumerate() {
+            for square in squares(bitboards.pieces_of(color, piece_type)) {
+                score.add(self.material[index], 1);
+                score.add(self.psts[index][relative_square(color, square)], 1);
+                let attacks = match piece_type {
+                    PieceType::Knight => knight_attacks(square),
+                    PieceType::Bishop => bishop_attacks(square, occupied),
+                    PieceType::Rook => rook_attacks(square, occupied),
+                    PieceType::Queen => queen_attacks(square, occupied),
+                    PieceType::Pawn | PieceType::King => continue,
+                };
+                let mobility = (attacks & !own & !pawn_covered).count_ones() as i32;
+                score.add(self.mobility[index], mobility);
+            }
+        }
+
+        self.add_pawn_structure(bitboards, color, &mut score);
+        self.add_king_safety(bitboards, color, &mut score);
+        if bitboards.pieces_of(color, PieceType::Bishop).count_ones() >= 2 {
+            score.add(self.bishop_pair, 1);
+        }
+        score
+    }
+
+    /// Adds the doubled, isolated and passed pawn terms of one side
+    fn add_pawn_structure(&self, bitboards: &Bitboards, color: Color, score: &mut Tapered) {
+        let pawns = bitboards.pieces_of(color, PieceType::Pawn);
+        let enemy_pawns = bitboards.pieces_of(color.reverse(), PieceType::Pawn);
+        for file in 0..8 {
+            let count = (pawns & (FILE_A << file)).count_ones() as i32;
+            if count > 1 {
+                score.add(self.doubled_pawn, count - 1);
+            }
+        }
+        for square in squares(pawns) {
+            let file = square % 8;
+            if pawns & neighbor_files(file) == 0 {
+                score.add(self.isolated_pawn, 1);
+            }
+            let front = front_span(color, square) & (neighbor_files(file) | FILE_A << file);
+            if enemy_pawns & front == 0 {
+                score.add(self.passed_pawn[relative_square(color, square) / 8], 1);
+            }
+        }
+    }
+
+    /// Adds the pawn shield and king zone attack terms of one side
+    fn add_king_safety(&self, bitboards: &Bitboards, color: Color, score: &mut Tapered) {
+        let king = match squares(bitboards.pieces_of(color, PieceType::King)).next() {
+            Some(king) => king,
+            None => return,
+        };
+        let files = neighbor_files(king % 8) | FILE_A << (king % 8);
+        let two_ranks = match color {
+            Color::White => (0xFFFF << (king - king % 8)) << 8,
+            Color::Black => (0xFFFF << (king - king % 8)) >> 16,
+        };
+        let shield = bitboards.pieces_of(color, PieceType::Pawn) & files & two_ranks;
+        score.add(self.pawn_shield, shield.count_ones() as i32);
+
+        let zone = king_attacks(king) | square_bit(king);
+        let enemy = color.reverse();
+        let occupied = bitboards.occupied();
+        let mut attacks = 0;
+        for piece_type in PIECE_TYPES {
+            for square in squares(bitboards.pieces_of(enemy, piece_type)) {
+                let attacked = match piece_type {
+                    PieceType::Pawn => pawn_attacks(enemy, square),
+                    PieceType::Knight => knight_attacks(square),
+                    PieceType::Bishop => bishop_attacks(square, occupied),
+                    PieceType::Rook => rook_attacks(square, occupied),
+                    PieceType::Queen => queen_attacks(square, occupied),
+                    PieceType::King => king_attacks(square),
+                };
+                attacks += (attacked & zone).count_ones() as i32;
+            }
+        }
+        score.add(self.king_zone_attack, attacks);
+    }
+}
+
+/// Scores a position in centipawns from the side to move's point of view with the default weights
+pub fn evaluate(board: &Board) -> i32 {
+    static DEFAULT: OnceLock<EvalWeights> = OnceLock::new();
+    DEFAULT.get_or_init(EvalWeights::default).evaluate(board)
+}
+
+/// Gets the square as seen from White, mirroring Black's squares across the middle of the board
+fn relative_square(color: Color, square: usize) -> usize {
+    match color {
+        Color::White => square,
+        Color::Black => square ^ 56,
+    }
+}
+
+/// Gets the files on either side of a file
+fn neighbor_files(file: usize) -> u64 {
+    let left = if file > 0 { FILE_A << (file - 1) } else { 0 };
+    let right = if file < 7 { FILE_A << (file + 1) } else { 0 };
+    left | right
+}
+
+/// Gets the ranks in front of a square from `color`'s side
+fn front_span(color: Color, square: usize) -> u64 {
+    let rank = square / 8;
+    match color {
+        Color::White if rank < 7 => u64::MAX << ((rank + 1) * 8),
+        Color::Black if rank > 0 => u64::MAX >> ((8 - rank) * 8),
+        _ => 0,
+    }
+}
+
+/// Combines middlegame and endgame tables written with rank 8 on top into one indexed from a1
+fn pst(mg: &[i32; 64], eg: &[i32; 64]) -> [Tapered; 64] {
+    let mut table = [Tapered::default(); 64];
+    for (square, weight) in table.iter_mut().enumerate() {
+        let row = 7 - square / 8;
+        let index = row * 8 + square % 8;
+        *weight = Tapered::new(mg[index], eg[index]);
+    }
+    table
+}
+
+#[rustfmt::skip]
+const PAWN_MG_PST: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+    50,  50,  50,  50,  50,  50,  50,  50,
+    10,  10,  20,  30,  30,  20,  10,  10,
+     5,   5,  10,  25,  25,  10,   5,   5,
+     0,   0,   0,  20,  20,   0,   0,   0,
+     5,  -5, -10,   0,   0, -10,  -5,   5,
+     5,  10,  10, -20, -20,  10,  10,   5,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const PAWN_EG_PST: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+    50,  50,  50,  50,  50,  50,  50,  50,
+    30,  30,  30,  30,  30,  30,  30,  30,
+    20,  20,  20,  20,  20,  20,  20,  20,
+    10,  10,  10,  10,  10,  10,  10,  10,
+     5,   5,   5,   5,   5,   5,   5,   5,
+     0,   0,   0,   0,   0,   0,   0,   0,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_PST: [i32; 64] = [
+   -50, -40, -30, -30, -30, -30, -40, -50,
+   -40, -20,   0,   0,   0,   0, -20, -40,
+   -30,   0,  10,  15,  15,  10,   0, -30,
+   -30,   5,  15,  20,  20,  15,   5, -30,
+   -30,   0,  15,  20,  20,  15,   0, -30,
+   -30,   5,  10,  15,  15,  10,   5, -30,
+   -40, -20,   0,   5,   5,   0, -20, -40,
+   -50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+#[rustfmt::skip]
+const BISHOP_PST: [i32; 64] = [
+   -20, -10, -10, -10, -10, -10, -10, -20,
+   -10,   0,   0,   0,   0,   0,   0, -10,
+   -10,   0,   5,  10,  10,   5,   0, -10,
+   -10,   5,   5,  10,  10,   5,   5, -10,
+   -10,   0,  10,  10,  10,  10,   0, -10,
+   -10,  10,  10,  10,  10,  10,  10, -10,
+   -10,   5,   0,   0,   0,   0,   5, -10,
+   -20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const ROOK_PST: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+     5,  10,  10,  10,  10,  10,  10,   5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+     0,   0,   0,   5,   5,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const QUEEN_PST: [i32; 64] = [
+   -20, -10, -10,  -5,  -5, -10, -10, -20,
+   -10,   0,   0,   0,   0,   0,   0, -10,
+   -10,   0,   5,   5,   5,   5,   0, -10,
+    -5,   0,   5,   5,   5,   5,   0,  -5,
+     0,   0,   5,   5,   5,   5,   0,  -5,
+   -10,   5,   5,   5,   5,   5,   0, -10,
+   -10,   0,   5,   0,   0,   0,   0, -10,
+   -20, -10, -10,  -5,  -5, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const KING_MG_PST: [i32; 64] = [
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -20, -30, -30, -40, -40, -30, -30, -20,
+   -10, -20, -20, -20, -20, -20, -20, -10,
+    20,  20,   0,   0,   0,   0,  20,  20,
+    20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+#[rustfmt::skip]
+const KING_EG_PST: [i32; 64] = [
+   -50, -40, -30, -20, -20, -30, -40, -50,
+   -30, -20, -10,   0,   0, -10, -20, -30,
+   -30, -10,  20,  30,  30,  20, -10, -30,
+   -30, -10,  30,  40,  40,  30, -10, -30,
+   -30, -10,  30,  40,  40,  30, -10, -30,
+   -30, -10,  20,  30,  30,  20, -10, -30,
+   -30, -30,   0,   0,   0,   0, -30, -30,
+   -50, -30, -30, -30, -30, -30, -30, -50,
+];
@@ -0,0 +1,228 @@
+pub mod eval;
+mod ordering;
+mod search;
+mod tt;
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::engine::eval::EvalWeights;
+use crate::chess::engine::ordering::OrderingTables;
+use crate::chess::engine::search::Searcher;
+use crate::chess::engine::tt::TranspositionTable;
+use crate::chess::uci::server::{SearchControl, UciPlayer};
+use crate::chess::uci::{GoParams, Info, Score, UciOption, UciOptionKind};
+
+/// Transposition table size of a new engine, in megabytes
+const DEFAULT_HASH_SIZE: usize = 16;
+
+/// When to end a search; a search without limits runs until it is stopped.
+///
+/// The first iteration always finishes, so there is a move to play however tight the limits are.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Deepest iteration in plies
+    pub depth: Option<u32>,
+    /// Nodes to search
+    pub nodes: Option<u64>,
+    /// Time to search
+    pub time: Option<Duration>,
+}
+
+impl SearchLimits {
+    /// Limits the search to a depth
+    pub fn depth(depth: u32) -> SearchLimits {
+        SearchLimits {
+            depth: Some(depth),
+            ..SearchLimits::default()
+        }
+    }
+
+    /// Limits the search to a number of nodes
+    pub fn nodes(nodes: u64) -> SearchLimits {
+        SearchLimits {
+            nodes: Some(nodes),
+            ..SearchLimits::default()
+        }
+    }
+
+    /// Limits the search to a time
+    pub fn time(time: Duration) -> SearchLimits {
+        SearchLimits {
+            time: Some(time),
+            ..SearchLimits::default()
+        }
+    }
+
+    /// Converts UCI `go` limits for the side to move in `board`, budgeting the clock with
+    /// `GoParams::time_budget`
+    pub fn from_go(params: &GoParams, board: &Board) -> SearchLimits {
+        SearchLimits {
+            depth: params.depth,
+            nodes: params.nodes,
+            time: params
+                .time_budget(board.color_to_move)
+                .map(Duration::from_millis),
+        }
+    }
+}
+
+/// Outcome of a search, or of one iteration of it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// Move to play, or `None` when there is no legal move
+    pub best_move: Option<Move>,
+    /// Evaluation for the side to move; `Mate(0)` when it is checkmated
+    pub score: Score,
+    /// Depth of the last finished iteration
+    pub depth: u32,
+    /// Nodes searched
+    pub nodes: u64,
+    /// Time searched
+    pub time: Duration,
+    /// Expected line of play, starting with the best move
+    pub pv: Vec<Move>,
+}
+
+impl SearchResult {
+    /// Converts to UCI search progress
+    pub fn to_info(&self) -> Info {
+        let millis = self.time.as_millis() as u64;
+        Info {
+            depth: Some(self.depth),
+            time: Some(millis),
+            nodes: Some(self.nodes),
+            nps: Some(self.nodes * 1000 / millis.max(1)),
+            score: Some(self.score),
+            pv: self.pv.clone(),
+            ..Info::default()
+        }
+    }
+}
+
+/// An iterative-deepening alpha-beta searcher.
+///
+/// The transposition table, killer moves and history scores carry over between searches; call
+/// `clear` when a new game starts.
+pub struct Engine {
+    tt: TranspositionTable,
+    tables: OrderingTables,
+    weights: EvalWeights,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Engine {
+    /// Creates an engine with a 16 MB transposition table
+    pub fn new() -> Engine {
+        Engine::with_hash_size(DEFAULT_HASH_SIZE)
+    }
+
+    /// Creates an engine with a transposition table of about `megabytes`
+    pub fn with_hash_size(megabytes: usize) -> Engine {
+        Engine {
+            tt: TranspositionTable::new(megabytes),
+            tables: OrderingTables::new(),
+            weights: EvalWeights::default(),
+        }
+    }
+
+    /// Replaces the transposition table with an empty one of about `megabytes`
+    pub fn set_hash_size(&mut self, megabytes: usize) {
+        self.tt = TranspositionTable::new(megabytes);
+    }
+
+    /// Gets the evaluation weights
+    pub fn weights(&self) -> &EvalWeights {
+        &self.weights
+    }
+
+    /// Replaces the evaluation weights, forgetting the scores learned with the old ones
+    pub fn set_weights(&mut self, weights: EvalWeights) {
+        self.weights = weights;
+        self.tt.clear();
+    }
+
+    /// Forgets what earlier searches learned
+    pub fn clear(&mut self) {
+        self.tt.clear();
+        self.tables.clear();
+    }
+
+    /// Searches `board` within `limits`
+    pub fn search(&mut self, board: &Board, limits: &SearchLimits) -> SearchResult {
+        self.search_with(board, limits, &|| false, &mut |_| {})
+    }
+
+    /// Searches `board` within `limits`, also ending soon after `stop` returns true, and passes each
+    /// finished iteration to `progress`
+    pub fn search_with(
+        &mut self,
+        board: &Board,
+        limits: &SearchLimits,
+        stop: &dyn Fn() -> bool,
+        progress: &mut dyn FnMut(&SearchResult),
+    ) -> SearchResult {
+        self.tables.new_search();
+        Searcher::new(&mut self.tt, &mut self.tables, &self.weights, *limits, stop)
+            .run(board, progress)
+    }
+}
+
+impl UciPlayer for Engine {
+    fn name(&self) -> String {
+        format!("Fenex {}", env!("CARGO_PKG_VERSION"))
+    }
+
+    fn author(&self) -> String {
+        "Small San".to_string()
+    }
+
+    fn options(&self) -> Vec<UciOption> {
+        vec![UciOption {
+            name: "Hash".to_string(),
+            kind: UciOptionKind::Spin {
+                default: DEFAULT_HASH_SIZE as i64,
+                min: 1,
+                max: 4096,
+            },
+        }]
+    }
+
+    fn set_option(&mut self, name: &str, value: Option<&str>) {
+        if name.eq_ignore_ascii_case("Hash") {
+            if let Some(megabytes) = value.and_then(|value| value.parse().ok()) {
+                self.set_hash_size(megabytes);
+            }
+        }
+    }
+
+    fn new_game(&mut self) {
+        self.clear();
+    }
+
+    fn go(
+        &mut self,
+        board: &Board,
+        params: &GoParams,
+        control: &mut SearchControl<'_>,
+    ) -> Option<Move> {
+        let limits = SearchLimits::from_go(params, board);
+        // Checking for `stop` and reporting progress both need the control
+        let control = RefCell::new(control);
+        let stop = || control.borrow().should_stop();
+        let mut progress = |result: &SearchResult| control.borrow_mut().report(&result.to_info());
+        let result = self.search_with(board, &limits, &stop, &mut progress);
+        // An infinite search may only answer once the GUI sends `stop`
+        if params.infinite {
+            control.borrow().wait_for_stop();
+        }
+        result.best_move
+    }
+}
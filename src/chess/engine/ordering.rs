@@ -0,0 +1,116 @@
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::piece::piece::{Color, PieceType};
+
+/// Deepest ply the search reaches, counting quiescence
+pub(crate) const MAX_PLY: usize = 128;
+
+/// Sort keys by move category; history scores stay below `KILLER`
+const TT_MOVE: i32 = 3_000_000;
+const CAPTURE: i32 = 2_000_000;
+const PROMOTION: i32 = 1_500_000;
+const KILLER: i32 = 1_000_000;
+const HISTORY_LIMIT: i32 = 500_000;
+
+/// Killer moves and history scores, learned from beta cutoffs, that order quiet moves
+pub(crate) struct OrderingTables {
+    /// Two quiet moves per ply that recently caused a cutoff
+    killers: Vec<[Option<Move>; 2]>,
+    /// Cutoff scores indexed by color, from square and to square
+    history: Vec<i32>,
+}
+
+impl OrderingTables {
+    /// Creates empty tables
+    pub fn new() -> OrderingTables {
+        OrderingTables {
+            killers: vec![[None; 2]; MAX_PLY],
+            history: vec![0; 2 * 64 * 64],
+        }
+    }
+
+    /// Prepares for a new search: killers belong to the old positions, and history fades
+    pub fn new_search(&mut self) {
+        self.killers.fill([None; 2]);
+        for score in &mut self.history {
+            *score /= 2;
+        }
+    }
+
+    /// Forgets everything, for a new game
+    pub fn clear(&mut self) {
+        self.killers.fill([None; 2]);
+        self.history.fill(0);
+    }
+
+    /// Remembers a quiet move of the side to move that caused a cutoff at `depth`
+    pub fn record_cutoff(&mut self, board: &Board, mv: &Move, ply: usize, depth: i32) {
+        if mv.is_capture() || mv.is_promotion() {
+            return;
+        }
+        if let Some(killers) = self.killers.get_mut(ply) {
+            if killers[0] != Some(*mv) {
+                killers[1] = killers[0];
+                killers[0] = Some(*mv);
+            }
+        }
+        let index = history_index(board.color_to_move, mv);
+        self.history[index] += depth * depth;
+        if self.history[index] > HISTORY_LIMIT {
+            for score in &mut self.history {
+                *score /= 2;
+            }
+        }
+    }
+
+    /// Sorts moves best first: the table move, captures by MVV-LVA, promotions, killers, then history
+    pub fn order(&self, board: &Board, moves: &mut [Move], ply: usize, tt_move: Option<Move>) {
+        moves.sort_by_cached_key(|mv| -self.score(board, mv, ply, tt_move));
+    }
+
+    /// Gets the sort key of a move
+    fn score(&self, board: &Board, mv: &Move, ply: usize, tt_move: Option<Move>) -> i32 {
+        if Some(*mv) == tt_move {
+            return TT_MOVE;
+        }
+        let promotion = mv.promotion.map_or(0, piece_value);
+        if let Some(victim) = mv.captured {
+            // Most valuable victim first, then least valuable attacker
+            let attacker = board
+                .get(mv.from)
+                .map_or(0, |piece| piece_value(piece.piece_type));
+            return CAPTURE + piece_value(victim) * 10 - attacker / 10 + promotion;
+        }
+        if mv.is_promotion() {
+            return PROMOTION + promotion;
+        }
+        match self.killers.get(ply) {
+            Some(killers) if killers[0] == Some(*mv) => KILLER + 1,
+            Some(killers) if killers[1] == Some(*mv) => KILLER,
+            _ => self.history[history_index(board.color_to_move, mv)],
+        }
+    }
+}
+
+/// Gets the material value of a piece in centipawns; kings count for nothing
+fn piece_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn => 100,
+        PieceType::Knight => 320,
+        PieceType::Bishop => 330,
+        PieceType::Rook => 500,
+        PieceType::Queen => 900,
+        PieceType::King => 0,
+    }
+}
+
+/// Gets the history slot of a move by `color`
+fn history_index(color: Color, mv: &Move) -> usize {
+    let side = match color {
+        Color::White => 0,
+        Color::Black => 1,
+    };
+    let from = mv.from.to_index().expect("valid coordinates");
+    let to = mv.to.to_index().expect("valid coordinates");
+    (side * 64 + from) * 64 + to
+}
@@ -0,0 +1,327 @@
+use std::time::{Duration, Instant};
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::engine::eval::EvalWeights;
+use crate::chess::engine::ordering::{OrderingTables, MAX_PLY};
+use crate::chess::engine::tt::{Bound, Entry, TranspositionTable};
+use crate::chess::engine::{SearchLimits, SearchResult};
+use crate::chess::piece::piece::PieceType;
+use crate::chess::uci::Score;
+
+/// Score of giving mate now; mates further away score one less per ply
+const MATE: i32 = 32000;
+
+/// Bound outside every score
+const INFINITY: i32 = MATE + 1;
+
+/// Scores beyond this are mates
+const MATE_BOUND: i32 = MATE - MAX_PLY as i32;
+
+/// Deepest iteration when no depth limit is given
+const MAX_DEPTH: u32 = 64;
+
+/// Nodes between checks of the clock and the stop callback
+const CHECK_INTERVAL: u64 = 1024;
+
+/// State of one search
+pub(crate) struct Searcher<'a> {
+    tt: &'a mut TranspositionTable,
+    tables: &'a mut OrderingTables,
+    weights: &'a EvalWeights,
+    limits: SearchLimits,
+    stop: &'a dyn Fn() -> bool,
+    start: Instant,
+    nodes: u64,
+    /// Set once a limit is hit; scores from then on are meaningless
+    aborted: bool,
+    /// Set after the first iteration, which always runs to the end so there is a move to play
+    can_abort: bool,
+    /// Hashes of the positions before the current one on the searched line, for repetitions
+    path: Vec<u64>,
+}
+
+impl<'a> Searcher<'a> {
+    /// Prepares a search that starts the clock now
+    pub fn new(
+        tt: &'a mut TranspositionTable,
+        tables: &'a mut OrderingTables,
+        weights: &'a EvalWeights,
+        limits: SearchLimits,
+        stop: &'a dyn Fn() -> bool,
+    ) -> Searcher<'a> {
+        Searcher {
+            tt,
+            tables,
+            weights,
+            limits,
+            stop,
+            start: Instant::now(),
+            nodes: 0,
+            aborted: false,
+            can_abort: false,
+            path: Vec::new(),
+        }
+    }
+
+    /// Deepens the search one ply at a time until a limit is hit, reporting each finished iteration
+    pub fn run(&mut self, board: &Board, progress: &mut dyn FnMut(&SearchResult)) -> SearchResult {
+        let mut board = board.clone();
+        let mut result = SearchResult {
+            best_move: None,
+            score: Score::Centipawns(0),
+            depth: 0,
+            nodes: 0,
+            time: Duration::ZERO,
+            pv: Vec::new(),
+        };
+        if board.generate_moves().is_empty() {
+            if board.is_in_check() {
+                result.score = Score::Mate(0);
+            }
+            return result;
+        }
+
+        let max_depth = self.limits.depth.unwrap_or(MAX_DEPTH).clamp(1, MAX_DEPTH);
+        for depth in 1..=max_depth {
+            let mut pv = Vec::new();
+            let score = self.negamax(&mut board, depth as i32, -INFINITY, INFINITY, 0, &mut pv);
+            if self.aborted {
+                break;
+            }
+            self.can_abort = true;
+            result = SearchResult {
+                best_move: pv.first().copied(),
+                score: to_score(score),
+                depth,
+                nodes: self.nodes,
+                time: self.start.elapsed(),
+                pv,
+            };
+            progress(&result);
+            // A mate found at this depth is the shortest one
+            if score.abs() > MATE_BOUND || (self.stop)() {
+                break;
+            }
+            // The next iteration would likely not finish in the time left
+            if let Some(time) = self.limits.time {
+                if self.start.elapsed() * 2 >= time {
+                    break;
+                }
+            }
+        }
+        result.nodes = self.nodes;
+        result.time = self.start.elapsed();
+        result
+    }
+
+    /// Searches `depth` plies below the position, returning its score for the side to move within
+    /// `alpha` and `beta`, and filling `pv` with the best line
+    fn negamax(
+        &mut self,
+        board: &mut Board,
+        depth: i32,
+        mut alpha: i32,
+        beta: i32,
+        ply: usize,
+        pv: &mut Vec<Move>,
+    ) -> i32 {
+        pv.clear();
+        if self.should_abort() {
+            return 0;
+        }
+        if ply > 0 && self.is_draw(board) {
+            return 0;
+        }
+        let in_check = board.is_in_check();
+        // Look one ply further at checks, so forcing lines are not cut short
+        let depth = if in_check { depth + 1 } else { depth };
+        if depth <= 0 || ply >= MAX_PLY - 1 {
+            return self.quiescence(board, alpha, beta, ply);
+        }
+        self.nodes += 1;
+
+        let key = board.zobrist_hash();
+        let mut tt_move = None;
+        if let Some(entry) = self.tt.probe(key) {
+            tt_move = entry.best_move;
+            if ply > 0 && entry.depth >= depth as u32 {
+                let score = score_from_tt(entry.score, ply);
+                match entry.bound {
+                    Bound::Exact => return score,
+                    Bound::Lower if score >= beta => return score,
+                    Bound::Upper if score <= alpha => return score,
+                    _ => {}
+                }
+            }
+        }
+
+        let mut moves = board.generate_moves();
+        if moves.is_empty() {
+            return if in_check { ply as i32 - MATE } else { 0 };
+        }
+        self.tables.order(board, &mut moves, ply, tt_move);
+
+        let original_alpha = alpha;
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+        let mut child_pv = Vec::new();
+        self.path.push(key);
+        for mv in moves {
+            let undo = board.execute_move(&mv);
+            let score = -self.negamax(board, depth - 1, -beta, -alpha, ply + 1, &mut child_pv);
+            board.unmake_move(undo);
+            if self.aborted {
+                self.path.pop();
+                return 0;
+            }
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mv);
+            }
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(mv);
+                pv.extend_from_slice(&child_pv);
+            }
+            if score >= beta {
+                self.tables.record_cutoff(board, &mv, ply, depth);
+                break;
+            }
+        }
+        self.path.pop();
+
+        let bound = if best_score >= beta {
+            Bound::Lower
+        } else if best_score > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        self.tt.store(Entry {
+            key,
+            depth: depth as u32,
+            score: score_to_tt(best_score, ply),
+            bound,
+            best_move,
+        });
+        best_score
+    }
+
+    /// Searches captures and queen promotions until the position is quiet, so the evaluation is
+    /// not taken in the middle of an exchange
+    fn quiescence(&mut self, board: &mut Board, mut alpha: i32, beta: i32, ply: usize) -> i32 {
+        if self.should_abort() {
+            return 0;
+        }
+        self.nodes += 1;
+        let moves = board.generate_moves();
+        if moves.is_empty() {
+            return if board.is_in_check() {
+                ply as i32 - MATE
+            } else {
+                0
+            };
+        }
+        // The side to move can usually do at least as well as standing still
+        let stand_pat = self.weights.evaluate(board);
+        if stand_pat >= beta || ply >= MAX_PLY - 1 {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut captures: Vec<Move> = moves
+            .into_iter()
+            .filter(|mv| mv.is_capture() || mv.promotion == Some(PieceType::Queen))
+            .collect();
+        self.tables.order(board, &mut captures, ply, None);
+        let mut best_score = stand_pat;
+        for mv in captures {
+            let undo = board.execute_move(&mv);
+            let score = -self.quiescence(board, -beta, -alpha, ply + 1);
+            board.unmake_move(undo);
+            if self.aborted {
+                return 0;
+            }
+            if score > best_score {
+                best_score = score;
+            }
+            if score > alpha {
+                alpha = score;
+            }
+            if score >= beta {
+                break;
+            }
+        }
+        best_score
+    }
+
+    /// Checks the limits, remembering once one is hit
+    fn should_abort(&mut self) -> bool {
+        if self.aborted || !self.can_abort {
+            return self.aborted;
+        }
+        if self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes) {
+            self.aborted = true;
+        } else if self.nodes.is_multiple_of(CHECK_INTERVAL) {
+            let out_of_time = self
+                .limits
+                .time
+                .is_some_and(|time| self.start.elapsed() >= time);
+            self.aborted = out_of_time || (self.stop)();
+        }
+        self.aborted
+    }
+
+    /// Checks for a draw by the fifty-move rule, a repetition on the searched line, or lack of material
+    fn is_draw(&self, board: &Board) -> bool {
+        if board.halfmove_clock >= 100 {
+            return true;
+        }
+        // Only positions with the same side to move since the last capture or pawn move can repeat
+        let key = board.zobrist_hash();
+        let repeated = self
+            .path
+            .iter()
+            .rev()
+            .take(board.halfmove_clock as usize)
+            .skip(1)
+            .step_by(2)
+            .any(|&previous| previous == key);
+        repeated || board.has_insufficient_material()
+    }
+}
+
+/// Converts a mate score from the root's point of view to one counted from the stored position
+fn score_to_tt(score: i32, ply: usize) -> i32 {
+    if score > MATE_BOUND {
+        score + ply as i32
+    } else if score < -MATE_BOUND {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+/// Converts a stored mate score back to one counted from the root
+fn score_from_tt(score: i32, ply: usize) -> i32 {
+    if score > MATE_BOUND {
+        score - ply as i32
+    } else if score < -MATE_BOUND {
+        score + ply as i32
+    } else {
+        score
+    }
+}
+
+/// Converts an internal score to centipawns or moves to mate
+fn to_score(score: i32) -> Score {
+    if score > MATE_BOUND {
+        Score::Mate((MATE - score + 1) / 2)
+    } else if score < -MATE_BOUND {
+        Score::Mate(-(MATE + score + 1) / 2)
+    } else {
+        Score::Centipawns(score)
+    }
+}
@@ -0,0 +1,70 @@
+use std::mem;
+
+use crate::chess::board::moves::Move;
+
+/// How a stored score relates to the true value of the position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Bound {
+    /// The score is exact
+    Exact,
+    /// The search failed high; the true score is at least this
+    Lower,
+    /// The search failed low; the true score is at most this
+    Upper,
+}
+
+/// A searched position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Entry {
+    /// Full Zobrist hash, to tell positions sharing a slot apart
+    pub key: u64,
+    /// Depth the position was searched to
+    pub depth: u32,
+    /// Score, with mate scores counted from this position
+    pub score: i32,
+    /// Kind of score
+    pub bound: Bound,
+    /// Best or refuting move, tried first when the position comes up again
+    pub best_move: Option<Move>,
+}
+
+/// Fixed-size table of searched positions indexed by Zobrist hash
+pub(crate) struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+}
+
+impl TranspositionTable {
+    /// Creates a table taking about `megabytes` of memory, with a power-of-two number of slots
+    pub fn new(megabytes: usize) -> TranspositionTable {
+        let slots = (megabytes.max(1) * 1024 * 1024 / mem::size_of::<Option<Entry>>()).max(1);
+        // Round down to a power of two so the index is a mask
+        let slots = 1 << (usize::BITS - 1 - slots.leading_zeros());
+        TranspositionTable {
+            entries: vec![None; slots],
+        }
+    }
+
+    /// Looks up a position
+    pub fn probe(&self, key: u64) -> Option<Entry> {
+        self.entries[self.index(key)].filter(|entry| entry.key == key)
+    }
+
+    /// Stores a position, keeping a deeper result for the same position
+    pub fn store(&mut self, entry: Entry) {
+        let index = self.index(entry.key);
+        match self.entries[index] {
+            Some(old) if old.key == entry.key && old.depth > entry.depth => {}
+            _ => self.entries[index] = Some(entry),
+        }
+    }
+
+    /// Forgets every position
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+
+    /// Gets the slot of a hash
+    fn index(&self, key: u64) -> usize {
+        key as usize & (self.entries.len() - 1)
+    }
+}
@@ -0,0 +1,279 @@
+use std::str::FromStr;
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::error::ChessError;
+
+/// An operation of an EPD record, with SAN operands resolved to moves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpdOperation {
+    /// `bm`: best moves
+    BestMoves(Vec<Move>),
+    /// `am`: moves to avoid
+    AvoidMoves(Vec<Move>),
+    /// `id`: position identifier
+    Id(String),
+    /// `ce`: centipawn evaluation from the side to move's point of view
+    CentipawnEvaluation(i32),
+    /// `acd`: analysis depth in plies
+    AnalysisDepth(u32),
+    /// `pv`: predicted variation, each move resolved after the previous one
+    PredictedVariation(Vec<Move>),
+    /// `c0` to `c9`: numbered comments
+    Comment(u8, String),
+    /// `hmvc`: halfmove clock
+    HalfmoveClock(u32),
+    /// `fmvn`: fullmove number
+    FullmoveNumber(u32),
+    /// Any other opcode with its raw operands
+    Other(String, Vec<String>),
+}
+
+impl EpdOperation {
+    /// Gets the opcode of the operation
+    pub fn opcode(&self) -> String {
+        match self {
+            EpdOperation::BestMoves(_) => "bm".to_string(),
+            EpdOperation::AvoidMoves(_) => "am".to_string(),
+            EpdOperation::Id(_) => "id".to_string(),
+            EpdOperation::CentipawnEvaluation(_) => "ce".to_string(),
+            EpdOperation::AnalysisDepth(_) => "acd".to_string(),
+            EpdOperation::PredictedVariation(_) => "pv".to_string(),
+            EpdOperation::Comment(n, _) => format!("c{}", n),
+            EpdOperation::HalfmoveClock(_) => "hmvc".to_string(),
+            EpdOperation::FullmoveNumber(_) => "fmvn".to_string(),
+            EpdOperation::Other(opcode, _) => opcode.clone(),
+        }
+    }
+}
+
+/// An Extended Position Description record: a position and its operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Epd {
+    /// The position; its clocks come from `hmvc` and `fmvn` when present
+    pub board: Board,
+    /// Operations in the order they appear
+    pub operations: Vec<EpdOperation>,
+}
+
+impl Epd {
+    /// Parses an EPD record: four FEN fields followed by `opcode operands;` operations
+    pub fn parse(epd: &str) -> Result<Epd, ChessError> {
+        let invalid =
+            |reason: &str| ChessError::InvalidEpd(format!("{} in {}", reason, epd.trim()));
+        let mut rest = epd.trim_start();
+        let mut fields = Vec::new();
+        for _ in 0..4 {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            if end == 0 {
+                return Err(invalid("missing position field"));
+            }
+            fields.push(&rest[..end]);
+            rest = rest[end..].trim_start();
+        }
+        let mut board = Board::from_fen(&format!("{} 0 1", fields.join(" ")))?;
+
+        let mut operations = Vec::new();
+        for (opcode, operands) in
+            split_operations(rest).ok_or_else(|| invalid("unterminated string"))?
+        {
+            let operation = match opcode.as_str() {
+                "bm" | "am" => {
+                    let moves = operands
+                        .iter()
+                        .map(|san| board.parse_san(san))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if opcode == "bm" {
+                        EpdOperation::BestMoves(moves)
+                    } else {
+                        EpdOperation::AvoidMoves(moves)
+                    }
+                }
+                "pv" => {
+                    let mut line = board.clone();
+                    let mut moves = Vec::new();
+                    for san in &operands {
+                        let mv = line.parse_san(san)?;
+                        line.apply_move(&mv)?;
+                        moves.push(mv);
+                    }
+                    EpdOperation::PredictedVariation(moves)
+                }
+                "id" => EpdOperation::Id(operands.join(" ")),
+                "ce" => EpdOperation::CentipawnEvaluation(parse_number(&operands, epd)?),
+                "acd" => EpdOperation::AnalysisDepth(parse_number(&operands, epd)?),
+                "hmvc" => {
+                    board.halfmove_clock = parse_number(&operands, epd)?;
+                    EpdOperation::HalfmoveClock(board.halfmove_clock)
+                }
+                "fmvn" => {
+                    board.fullmove_number = parse_number(&operands, epd)?;
+                    EpdOperation::FullmoveNumber(board.fullmove_number)
+                }
+                _ => match comment_number(&opcode) {
+                    Some(n) => EpdOperation::Comment(n, operands.join(" ")),
+                    None => EpdOperation::Other(opcode, operands),
+                },
+            };
+            operations.push(operation);
+        }
+        Ok(Epd { board, operations })
+    }
+
+    /// Gets the first operation with an opcode
+    pub fn operation(&self, opcode: &str) -> Option<&EpdOperation> {
+        self.operations.iter().find(|op| op.opcode() == opcode)
+    }
+
+    /// Gets the `id` of the record
+    pub fn id(&self) -> Option<&str> {
+        self.operations.iter().find_map(|op| match op {
+            EpdOperation::Id(id) => Some(id.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Gets the best moves (`bm`) of the record, or none if it has no `bm` operation
+    pub fn best_moves(&self) -> &[Move] {
+        self.operations
+            .iter()
+            .find_map(|op| match op {
+                EpdOperation::BestMoves(moves) => Some(moves.as_slice()),
+                _ => None,
+            })
+            .unwrap_or(&[])
+    }
+
+    /// Serializes the record, writing moves in SAN.
+    ///
+    /// Fails if a move is not legal in its position or a string operand contains `"`, which EPD
+    /// cannot escape.
+    pub fn to_epd(&self) -> Result<String, ChessError> {
+        let fen = self.board.to_fen();
+        let mut epd = fen.split_whitespace().take(4).collect::<Vec<_>>().join(" ");
+        for operation in &self.operations {
+            let operands = match operation {
+                EpdOperation::BestMoves(moves) | EpdOperation::AvoidMoves(moves) => moves
+                    .iter()
+                    .map(|mv| self.board.to_san(mv))
+                    .collect::<Result<_, _>>()?,
+                EpdOperation::PredictedVariation(moves) => {
+                    let mut line = self.board.clone();
+                    let mut sans = Vec::new();
+                    for mv in moves {
+                        sans.push(line.to_san(mv)?);
+                        line.apply_move(mv)?;
+                    }
+                    sans
+                }
+                EpdOperation::Id(text) | EpdOperation::Comment(_, text) => {
+                    vec![quote_string(text)?]
+                }
+                EpdOperation::CentipawnEvaluation(value) => vec![value.to_string()],
+                EpdOperation::AnalysisDepth(value)
+                | EpdOperation::HalfmoveClock(value)
+                | EpdOperation::FullmoveNumber(value) => vec![value.to_string()],
+                EpdOperation::Other(_, operands) => operands
+                    .iter()
+                    .map(|operand| quote_operand(operand))
+                    .collect::<Result<_, _>>()?,
+            };
+            epd.push(' ');
+            epd.push_str(&operation.opcode());
+            for operand in operands {
+                epd.push(' ');
+                epd.push_str(&operand);
+            }
+            epd.push(';');
+        }
+        Ok(epd)
+    }
+}
+
+/// Quotes a string operand; EPD has no escape for `"`, so a string containing one is rejected
+fn quote_string(text: &str) -> Result<String, ChessError> {
+    if text.contains('"') {
+        return Err(ChessError::InvalidEpd(format!(
+            "string {} contains a double quote",
+            text
+        )));
+    }
+    Ok(format!("\"{}\"", text))
+}
+
+/// Quotes an operand that would not read back as one word
+fn quote_operand(operand: &str) -> Result<String, ChessError> {
+    if operand.is_empty() || operand.contains(|c: char| c.is_whitespace() || c == ';' || c == '"') {
+        quote_string(operand)
+    } else {
+        Ok(operand.to_string())
+    }
+}
+
+/// Parses the single numeric operand of an operation
+fn parse_number<T: FromStr>(operands: &[String], epd: &str) -> Result<T, ChessError> {
+    match operands {
+        [value] => value.parse().map_err(|_| {
+            ChessError::InvalidEpd(format!("invalid number {} in {}", value, epd.trim()))
+        }),
+        _ => Err(ChessError::InvalidEpd(format!(
+            "expected one number in {}",
+            epd.trim()
+        ))),
+    }
+}
+
+/// Gets the number of a `c0` to `c9` comment opcode
+fn comment_number(opcode: &str) -> Option<u8> {
+    match opcode.as_bytes() {
+        [b'c', digit @ b'0'..=b'9'] => Some(digit - b'0'),
+        _ => None,
+    }
+}
+
+/// Splits the operation section into opcodes and operands, keeping quoted strings whole.
+///
+/// Returns `None` if a string is not closed.
+fn split_operations(text: &str) -> Option<Vec<(String, Vec<String>)>> {
+    let mut operations = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            ';' => {
+                if !words.is_empty() {
+                    let opcode = words.remove(0);
+                    operations.push((opcode, std::mem::take(&mut words)));
+                }
+            }
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        c => string.push(c),
+                    }
+                }
+                words.push(string);
+            }
+            _ => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == ';' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                words.push(word);
+            }
+        }
+    }
+    // The last operation may omit its semicolon
+    if !words.is_empty() {
+        let opcode = words.remove(0);
+        operations.push((opcode, words));
+    }
+    Some(operations)
+}
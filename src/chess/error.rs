@@ -0,0 +1,94 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::chess::board::fen::FenError;
+use crate::chess::piece::piece::PieceType;
+
+/// Errors returned by fenex
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChessError {
+    /// A move that is not legal in the position, as written by the caller
+    IllegalMove(String),
+    /// Promotion to a piece other than queen, rook, bishop or knight
+    InvalidPromotion(PieceType),
+    /// A promotion move given without the piece to promote to
+    MissingPromotion(String),
+    /// A SAN move that matches more than one legal move
+    AmbiguousMove(String),
+    /// A malformed SAN move
+    InvalidSan(String),
+    /// A malformed UCI move
+    InvalidUci(String),
+    /// A malformed or inconsistent FEN record
+    InvalidFen(FenError),
+    /// Coordinates outside the board or a malformed "x,y" string
+    InvalidCoordinates(String),
+    /// A malformed algebraic square such as "i9"
+    InvalidNotation(String),
+    /// Malformed PGN input
+    InvalidPgn(String),
+    /// A malformed Polyglot opening book
+    InvalidBook(String),
+    /// A malformed EPD record
+    InvalidEpd(String),
+    /// A malformed evaluation weights file
+    InvalidEvalWeights(String),
+    /// Reading or writing a stream failed
+    Io(String),
+    /// An engine process that failed or broke its protocol
+    Engine(String),
+    /// A move or decision after the game has ended
+    GameOver,
+    /// A draw claim that no rule supports in the current position
+    NoDrawClaim,
+}
+
+impl fmt::Display for ChessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChessError::IllegalMove(mv) => write!(f, "Illegal move: {}", mv),
+            ChessError::InvalidPromotion(piece) => {
+                write!(f, "Invalid promotion piece: {:?}", piece)
+            }
+            ChessError::MissingPromotion(mv) => write!(f, "Missing promotion piece: {}", mv),
+            ChessError::AmbiguousMove(mv) => write!(f, "Ambiguous move: {}", mv),
+            ChessError::InvalidSan(san) => write!(f, "Invalid SAN move: {}", san),
+            ChessError::InvalidUci(uci) => write!(f, "Invalid UCI move: {}", uci),
+            ChessError::InvalidFen(error) => write!(f, "{}", error),
+            ChessError::InvalidCoordinates(input) => write!(f, "Invalid coordinates: {}", input),
+            ChessError::InvalidNotation(input) => write!(f, "Invalid notation: {}", input),
+            ChessError::InvalidPgn(reason) => write!(f, "Invalid PGN: {}", reason),
+            ChessError::InvalidBook(reason) => write!(f, "Invalid Polyglot book: {}", reason),
+            ChessError::InvalidEpd(reason) => write!(f, "Invalid EPD: {}", reason),
+            ChessError::InvalidEvalWeights(reason) => {
+                write!(f, "Invalid evaluation weights: {}", reason)
+            }
+            ChessError::Engine(reason) => write!(f, "Engine error: {}", reason),
+            ChessError::Io(reason) => write!(f, "I/O error: {}", reason),
+            ChessError::GameOver => write!(f, "Game is over"),
+            ChessError::NoDrawClaim => write!(f, "No draw can be claimed"),
+        }
+    }
+}
+
+impl Error for ChessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ChessError::InvalidFen(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<FenError> for ChessError {
+    fn from(error: FenError) -> Self {
+        ChessError::InvalidFen(error)
+    }
+}
+
+impl From<io::Error> for ChessError {
+    fn from(error: io::Error) -> Self {
+        ChessError::Io(error.to_string())
+    }
+}
@@ -0,0 +1,250 @@
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::error::ChessError;
+use crate::chess::pgn::{GameResult, PgnGame};
+use crate::chess::piece::piece::Color;
+
+/// How a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The side to move is checkmated
+    Checkmate { winner: Color },
+    /// The side to move has no legal moves and is not in check
+    Stalemate,
+    /// Draw claimed after the same position occurred three times
+    ThreefoldRepetition,
+    /// Automatic draw after the same position occurred five times
+    FivefoldRepetition,
+    /// Draw claimed after fifty moves by each side without a pawn move or capture
+    FiftyMoveRule,
+    /// Automatic draw after seventy-five moves by each side without a pawn move or capture
+    SeventyFiveMoveRule,
+    /// Neither side can checkmate
+    InsufficientMaterial,
+    /// A player resigned
+    Resignation { winner: Color },
+    /// A player ran out of time; drawn if the opponent cannot checkmate
+    Timeout { winner: Option<Color> },
+    /// Both players agreed to a draw
+    DrawByAgreement,
+}
+
+impl Outcome {
+    /// Gets the winning color, or `None` for a draw
+    pub fn winner(&self) -> Option<Color> {
+        match *self {
+            Outcome::Checkmate { winner } | Outcome::Resignation { winner } => Some(winner),
+            Outcome::Timeout { winner } => winner,
+            _ => None,
+        }
+    }
+
+    /// Checks if the outcome is a draw
+    pub fn is_draw(&self) -> bool {
+        self.winner().is_none()
+    }
+
+    /// Converts to a PGN game termination marker
+    pub fn result(&self) -> GameResult {
+        match self.winner() {
+            Some(Color::White) => GameResult::WhiteWins,
+            Some(Color::Black) => GameResult::BlackWins,
+            None => GameResult::Draw,
+        }
+    }
+}
+
+/// A game in progress: the current board, the moves played and the outcome once decided
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    start: Board,
+    board: Board,
+    moves: Vec<Move>,
+    /// Repetition keys of every position reached, starting with the initial one
+    positions: Vec<String>,
+    outcome: Option<Outcome>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    /// Starts a game from the standard starting position
+    pub fn new() -> Self {
+        Self::from_board(Board::starting_position())
+    }
+
+    /// Starts a game from an arbitrary position
+    pub fn from_board(board: Board) -> Self {
+        let mut game = Self {
+            start: board.clone(),
+            positions: vec![position_key(&board)],
+            board,
+            moves: Vec::new(),
+            outcome: None,
+        };
+        game.outcome = game.automatic_outcome();
+        game
+    }
+
+    /// Gets the current position
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Gets the position the game started from
+    pub fn starting_board(&self) -> &Board {
+        &self.start
+    }
+
+    /// Gets the moves played so far
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Gets the outcome, if the game has ended
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    /// Checks if the game has ended
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// Plays a legal move and records it, ending the game if an automatic rule applies
+    pub fn play_move(&mut self, mv: &Move) -> Result<(), ChessError> {
+        if self.is_over() {
+            return Err(ChessError::GameOver);
+        }
+        let mv = self.board.resolve_move(mv)?;
+        self.board.execute_move(&mv);
+        self.moves.push(mv);
+        self.positions.push(position_key(&self.board));
+        self.outcome = self.automatic_outcome();
+        Ok(())
+    }
+
+    /// Plays a move given in Standard Algebraic Notation
+    pub fn play_san(&mut self, san: &str) -> Result<Move, ChessError> {
+        let mv = self.board.parse_san(san)?;
+        self.play_move(&mv)?;
+        Ok(mv)
+    }
+
+    /// Counts how many times the current position has occurred
+    pub fn repetition_count(&self) -> usize {
+        let current = self
+            .positions
+            .last()
+            .expect("positions always has the start");
+        self.positions.iter().filter(|key| *key == current).count()
+    }
+
+    /// Checks if the current position has occurred at least three times
+    pub fn can_claim_threefold_repetition(&self) -> bool {
+        self.repetition_count() >= 3
+    }
+
+    /// Checks if the last fifty moves by each side contained no pawn move or capture
+    pub fn can_claim_fifty_move_rule(&self) -> bool {
+        self.board.halfmove_clock >= 100
+    }
+
+    /// Claims a draw by threefold repetition or the fifty-move rule in the current position
+    pub fn claim_draw(&mut self) -> Result<Outcome, ChessError> {
+        if self.is_over() {
+            return Err(ChessError::GameOver);
+        }
+        let outcome = if self.can_claim_threefold_repetition() {
+            Outcome::ThreefoldRepetition
+        } else if self.can_claim_fifty_move_rule() {
+            Outcome::FiftyMoveRule
+        } else {
+            return Err(ChessError::NoDrawClaim);
+        };
+        self.outcome = Some(outcome);
+        Ok(outcome)
+    }
+
+    /// Claims a draw based on the position after `mv`, as when writing the move on the scoresheet.
+    ///
+    /// Following FIDE rules the move is played even if the claim turns out to be incorrect.
+    pub fn claim_draw_with_move(&mut self, mv: &Move) -> Result<Outcome, ChessError> {
+        self.play_move(mv)?;
+        self.claim_draw()
+    }
+
+    /// Ends the game by resignation of `color`
+    pub fn resign(&mut self, color: Color) -> Result<Outcome, ChessError> {
+        self.finish(Outcome::Resignation {
+            winner: color.reverse(),
+        })
+    }
+
+    /// Ends the game because `color` ran out of time; drawn if the opponent cannot checkmate
+    pub fn flag(&mut self, color: Color) -> Result<Outcome, ChessError> {
+        let opponent = color.reverse();
+        self.finish(Outcome::Timeout {
+            winner: Some(opponent).filter(|&winner| self.board.can_possibly_win(winner)),
+        })
+    }
+
+    /// Ends the game in a draw agreed by both players
+    pub fn agree_draw(&mut self) -> Result<Outcome, ChessError> {
+        self.finish(Outcome::DrawByAgreement)
+    }
+
+    /// Exports the game for PGN output
+    pub fn to_pgn(&self) -> Result<PgnGame, ChessError> {
+        let result = self.outcome.map_or(GameResult::Unknown, |o| o.result());
+        PgnGame::from_moves(&self.start, &self.moves, result)
+    }
+
+    /// Records an outcome decided by the players or the clock
+    fn finish(&mut self, outcome: Outcome) -> Result<Outcome, ChessError> {
+        if self.is_over() {
+            return Err(ChessError::GameOver);
+        }
+        self.outcome = Some(outcome);
+        Ok(outcome)
+    }
+
+    /// Finds an outcome that ends the game without a claim
+    fn automatic_outcome(&self) -> Option<Outcome> {
+        if self.board.generate_moves().is_empty() {
+            return Some(if self.board.is_in_check() {
+                Outcome::Checkmate {
+                    winner: self.board.color_to_move.reverse(),
+                }
+            } else {
+                Outcome::Stalemate
+            });
+        }
+        if self.repetition_count() >= 5 {
+            return Some(Outcome::FivefoldRepetition);
+        }
+        if self.board.halfmove_clock >= 150 {
+            return Some(Outcome::SeventyFiveMoveRule);
+        }
+        if self.board.has_insufficient_material() {
+            return Some(Outcome::InsufficientMaterial);
+        }
+        None
+    }
+}
+
+/// Identifies a position for repetition: placement, side to move, castling rights and a capturable en passant square
+fn position_key(board: &Board) -> String {
+    let fen = board.to_fen();
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    let en_passant = if board.generate_moves().iter().any(Move::is_en_passant) {
+        fields[3]
+    } else {
+        "-"
+    };
+    format!("{} {} {} {}", fields[0], fields[1], fields[2], en_passant)
+}
@@ -1,2 +1,10 @@
 pub mod board;
-pub mod piece;
\ No newline at end of file
+pub mod engine;
+pub mod epd;
+pub mod error;
+pub mod game;
+pub mod pgn;
+pub mod piece;
+pub mod polyglot;
+pub mod uci;
+pub mod xboard;
@@ -0,0 +1,119 @@
+pub mod reader;
+pub mod writer;
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::error::ChessError;
+
+/// Game termination marker of a PGN game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    /// "1-0"
+    WhiteWins,
+    /// "0-1"
+    BlackWins,
+    /// "1/2-1/2"
+    Draw,
+    /// "*" (game in progress, abandoned or unknown)
+    Unknown,
+}
+
+impl GameResult {
+    /// Parses a PGN result token
+    pub fn from_token(token: &str) -> Option<GameResult> {
+        match token {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None,
+        }
+    }
+
+    /// Gets the PGN result token
+    pub fn to_token(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Unknown => "*",
+        }
+    }
+}
+
+/// A move in PGN movetext with its annotations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnMove {
+    /// The move in Standard Algebraic Notation as written in the source
+    pub san: String,
+    /// The move resolved against the position it was played in
+    pub mv: Move,
+    /// Numeric Annotation Glyphs ("$1", or "!" written as 1)
+    pub nags: Vec<u8>,
+    /// Comment placed before the move
+    pub comment_before: Option<String>,
+    /// Comment placed after the move
+    pub comment: Option<String>,
+    /// Alternative lines replacing this move
+    pub variations: Vec<Vec<PgnMove>>,
+}
+
+impl PgnMove {
+    /// Creates an unannotated move
+    pub fn new(san: String, mv: Move) -> PgnMove {
+        PgnMove {
+            san,
+            mv,
+            nags: Vec::new(),
+            comment_before: None,
+            comment: None,
+            variations: Vec::new(),
+        }
+    }
+}
+
+/// A single game from a PGN file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    /// Tag pairs in the order they appear
+    pub tags: Vec<(String, String)>,
+    /// Main line moves
+    pub moves: Vec<PgnMove>,
+    /// Game termination marker
+    pub result: GameResult,
+}
+
+impl PgnGame {
+    /// Gets the value of a tag pair
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets a tag pair, replacing an existing value
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(key, _)| key == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Gets the position the game starts from, honouring the FEN tag
+    pub fn starting_board(&self) -> Result<Board, ChessError> {
+        match self.tag("FEN") {
+            Some(fen) => Board::from_fen(fen),
+            None => Ok(Board::starting_position()),
+        }
+    }
+
+    /// Replays the main line and returns the final position
+    pub fn final_board(&self) -> Result<Board, ChessError> {
+        let mut board = self.starting_board()?;
+        for pgn_move in &self.moves {
+            board.apply_move(&pgn_move.mv)?;
+        }
+        Ok(board)
+    }
+}
@@ -0,0 +1,367 @@
+use std::io::{BufRead, BufReader, Lines, Read};
+
+use crate::chess::board::board::Board;
+use crate::chess::error::ChessError;
+use crate::chess::pgn::{GameResult, PgnGame, PgnMove};
+
+/// Movetext token
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Move(String),
+    Nag(u8),
+    Comment(String),
+    OpenVariation,
+    CloseVariation,
+    Result(GameResult),
+}
+
+/// Streams games one at a time from any `Read` source
+pub struct PgnReader<R: Read> {
+    lines: Lines<BufReader<R>>,
+    pending: Option<String>,
+}
+
+impl<R: Read> PgnReader<R> {
+    /// Creates a reader over a PGN source
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: BufReader::new(reader).lines(),
+            pending: None,
+        }
+    }
+
+    /// Reads the tag section and movetext of the next game
+    fn next_game_text(&mut self) -> Option<Result<(String, String), ChessError>> {
+        let mut tags = String::new();
+        let mut movetext = String::new();
+        let mut in_comment = false;
+        let mut depth = 0;
+        loop {
+            let line = match self.pending.take() {
+                Some(line) => line,
+                None => match self.lines.next() {
+                    Some(Ok(line)) => line,
+                    Some(Err(e)) => return Some(Err(e.into())),
+                    None => break,
+                },
+            };
+            let trimmed = line.trim();
+            if !in_comment {
+                // Escape mechanism: lines starting with '%' are ignored
+                if trimmed.starts_with('%') {
+                    continue;
+                }
+                if trimmed.starts_with('[') {
+                    if !movetext.trim().is_empty() {
+                        // Tag of the next game
+                        self.pending = Some(line);
+                        break;
+                    }
+                    tags.push_str(trimmed);
+                    tags.push('\n');
+                    continue;
+                }
+            }
+            movetext.push_str(&line);
+            movetext.push('\n');
+            if ends_with_result(&line, &mut in_comment, &mut depth) {
+                break;
+            }
+        }
+        if tags.is_empty() && movetext.trim().is_empty() {
+            return None;
+        }
+        Some(Ok((tags, movetext)))
+    }
+}
+
+impl<R: Read> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, ChessError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(match self.next_game_text()? {
+            Ok((tags, movetext)) => parse_game(&tags, &movetext),
+            Err(e) => Err(e),
+        })
+    }
+}
+
+/// Parses every game in a PGN string
+pub fn parse_pgn(text: &str) -> Result<Vec<PgnGame>, ChessError> {
+    PgnReader::new(text.as_bytes()).collect()
+}
+
+/// Parses a single game from its tag section and movetext
+fn parse_game(tags: &str, movetext: &str) -> Result<PgnGame, ChessError> {
+    let mut game = PgnGame {
+        tags: parse_tags(tags)?,
+        moves: Vec::new(),
+        result: GameResult::Unknown,
+    };
+    let board = game.starting_board()?;
+    let tokens = tokenize(movetext)?;
+    let mut result = None;
+    let mut pos = 0;
+    game.moves = parse_line(&tokens, &mut pos, board, 0, &mut result)?;
+    game.result = result
+        .or_else(|| game.tag("Result").and_then(GameResult::from_token))
+        .unwrap_or(GameResult::Unknown);
+    Ok(game)
+}
+
+/// Tracks comment and variation nesting over a line; returns true if it ends on a top-level result
+fn ends_with_result(line: &str, in_comment: &mut bool, depth: &mut i32) -> bool {
+    let mut code_end = line.len();
+    for (i, c) in line.char_indices() {
+        if *in_comment {
+            if c == '}' {
+                *in_comment = false;
+            }
+            continue;
+        }
+        match c {
+            '{' => *in_comment = true,
+            '(' => *depth += 1,
+            ')' => *depth -= 1,
+            ';' => {
+                code_end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+    !*in_comment
+        && *depth == 0
+        && line[..code_end]
+            .split_whitespace()
+            .last()
+            .and_then(GameResult::from_token)
+            .is_some()
+}
+
+/// Parses `[Name "Value"]` tag pairs
+fn parse_tags(text: &str) -> Result<Vec<(String, String)>, ChessError> {
+    let invalid = |name: &str| ChessError::InvalidPgn(format!("invalid tag pair [{}", name));
+    let mut tags = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '[' {
+            continue;
+        }
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || c == '"' {
+                break;
+            }
+            name.push(c);
+            chars.next();
+        }
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.next() != Some('"') {
+            return Err(invalid(&name));
+        }
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some('\\') => match chars.next() {
+                    Some(escaped) => value.push(escaped),
+                    None => return Err(invalid(&name)),
+                },
+                Some('"') => break,
+                Some(c) => value.push(c),
+                None => return Err(invalid(&name)),
+            }
+        }
+        loop {
+            match chars.next() {
+                Some(']') => break,
+                Some(c) if c.is_whitespace() => {}
+                _ => return Err(invalid(&name)),
+            }
+        }
+        if name.is_empty() {
+            return Err(invalid(&name));
+        }
+        tags.push((name, value));
+    }
+    Ok(tags)
+}
+
+/// Splits movetext into tokens, dropping move numbers
+fn tokenize(text: &str) -> Result<Vec<Token>, ChessError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '{' => {
+                let mut comment = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => comment.push(c),
+                        None => {
+                            return Err(ChessError::InvalidPgn("unterminated comment".to_string()))
+                        }
+                    }
+                }
+                tokens.push(Token::Comment(comment.trim().to_string()));
+            }
+            ';' => {
+                let mut comment = String::new();
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                    comment.push(c);
+                }
+                tokens.push(Token::Comment(comment.trim().to_string()));
+            }
+            '(' => tokens.push(Token::OpenVariation),
+            ')' => tokens.push(Token::CloseVariation),
+            '$' => {
+                let mut digits = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    digits.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Nag(digits.parse().map_err(|_| {
+                    ChessError::InvalidPgn(format!("invalid annotation glyph ${}", digits))
+                })?));
+            }
+            _ => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "{}();$".contains(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                push_word(&mut tokens, &word)?;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Classifies a movetext word as result, move number, or move with optional glyph suffix
+fn push_word(tokens: &mut Vec<Token>, word: &str) -> Result<(), ChessError> {
+    if let Some(result) = GameResult::from_token(word) {
+        tokens.push(Token::Result(result));
+        return Ok(());
+    }
+    let mut word = word;
+    if !word.starts_with("0-0") {
+        // Move number indication such as "12." or "12..."
+        word = word.trim_start_matches(|c: char| c.is_ascii_digit());
+        word = word.trim_start_matches('.');
+    }
+    if word.is_empty() {
+        return Ok(());
+    }
+    if word == "--" {
+        return Err(ChessError::InvalidPgn(
+            "null moves are not supported".to_string(),
+        ));
+    }
+    let san = word.trim_end_matches(['!', '?']);
+    if san.is_empty() {
+        return Err(ChessError::InvalidPgn(format!("invalid move {}", word)));
+    }
+    tokens.push(Token::Move(san.to_string()));
+    let nag = match &word[san.len()..] {
+        "" => return Ok(()),
+        "!" => 1,
+        "?" => 2,
+        "!!" => 3,
+        "??" => 4,
+        "!?" => 5,
+        "?!" => 6,
+        _ => {
+            return Err(ChessError::InvalidPgn(format!(
+                "invalid move suffix {}",
+                word
+            )))
+        }
+    };
+    tokens.push(Token::Nag(nag));
+    Ok(())
+}
+
+/// Parses a line of moves (the main line or a variation), replaying it from `board`
+fn parse_line(
+    tokens: &[Token],
+    pos: &mut usize,
+    mut board: Board,
+    depth: usize,
+    result: &mut Option<GameResult>,
+) -> Result<Vec<PgnMove>, ChessError> {
+    let mut moves: Vec<PgnMove> = Vec::new();
+    // Position before the last move, where its variations start
+    let mut before = board.clone();
+    let mut pending_comment: Option<String> = None;
+    while let Some(token) = tokens.get(*pos) {
+        *pos += 1;
+        match token {
+            Token::Move(san) => {
+                let mv = board.parse_san(san)?;
+                before = board.clone();
+                board.apply_move(&mv)?;
+                let mut pgn_move = PgnMove::new(san.clone(), mv);
+                pgn_move.comment_before = pending_comment.take();
+                moves.push(pgn_move);
+            }
+            Token::Nag(nag) => {
+                if let Some(last) = moves.last_mut() {
+                    last.nags.push(*nag);
+                }
+            }
+            Token::Comment(comment) => {
+                let target = match moves.last_mut() {
+                    Some(last) => &mut last.comment,
+                    None => &mut pending_comment,
+                };
+                *target = Some(match target.take() {
+                    Some(existing) => format!("{} {}", existing, comment),
+                    None => comment.clone(),
+                });
+            }
+            Token::OpenVariation => {
+                if moves.is_empty() {
+                    return Err(ChessError::InvalidPgn(
+                        "variation without a preceding move".to_string(),
+                    ));
+                }
+                let variation = parse_line(tokens, pos, before.clone(), depth + 1, result)?;
+                if let Some(last) = moves.last_mut() {
+                    last.variations.push(variation);
+                }
+            }
+            Token::CloseVariation => {
+                if depth == 0 {
+                    return Err(ChessError::InvalidPgn("unbalanced parenthesis".to_string()));
+                }
+                return Ok(moves);
+            }
+            Token::Result(game_result) => {
+                if depth > 0 {
+                    return Err(ChessError::InvalidPgn(
+                        "result inside a variation".to_string(),
+                    ));
+                }
+                *result = Some(*game_result);
+                return Ok(moves);
+            }
+        }
+    }
+    if depth > 0 {
+        return Err(ChessError::InvalidPgn("unterminated variation".to_string()));
+    }
+    Ok(moves)
+}
@@ -0,0 +1,181 @@
+use crate::chess::board::board::{Board, STARTING_FEN};
+use crate::chess::board::moves::Move;
+use crate::chess::error::ChessError;
+use crate::chess::pgn::{GameResult, PgnGame, PgnMove};
+use crate::chess::piece::piece::Color;
+
+/// Maximum line length of exported movetext
+const LINE_WIDTH: usize = 80;
+
+/// Tags of the Seven Tag Roster with their default values, in export order
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+
+impl PgnGame {
+    /// Builds a game from moves played from `start`, resolving each against the position
+    pub fn from_moves(
+        start: &Board,
+        moves: &[Move],
+        result: GameResult,
+    ) -> Result<PgnGame, ChessError> {
+        let mut game = PgnGame {
+            tags: Vec::new(),
+            moves: Vec::new(),
+            result,
+        };
+        let fen = start.to_fen();
+        if fen != STARTING_FEN {
+            game.set_tag("SetUp", "1");
+            game.set_tag("FEN", &fen);
+        }
+        let mut board = start.clone();
+        for mv in moves {
+            let mv = board.resolve_move(mv)?;
+            game.moves.push(PgnMove::new(board.to_san(&mv)?, mv));
+            board.execute_move(&mv);
+        }
+        Ok(game)
+    }
+
+    /// Exports the game in PGN export format
+    pub fn to_pgn(&self) -> Result<String, ChessError> {
+        let mut pgn = String::new();
+
+        // Seven Tag Roster first, then the setup tags and any remaining tags
+        for (name, default) in SEVEN_TAG_ROSTER {
+            let value = match name {
+                "Result" => self.result.to_token(),
+                _ => self.tag(name).unwrap_or(default),
+            };
+            push_tag(&mut pgn, name, value);
+        }
+        let start = self.starting_board()?;
+        let fen = start.to_fen();
+        if fen != STARTING_FEN {
+            push_tag(&mut pgn, "SetUp", "1");
+            push_tag(&mut pgn, "FEN", &fen);
+        }
+        for (name, value) in &self.tags {
+            let exported = SEVEN_TAG_ROSTER.iter().any(|(tag, _)| tag == name)
+                || name == "SetUp"
+                || name == "FEN";
+            if !exported {
+                push_tag(&mut pgn, name, value);
+            }
+        }
+        pgn.push('\n');
+
+        // Movetext
+        let mut tokens = Vec::new();
+        push_line(
+            &mut tokens,
+            &self.moves,
+            &start,
+            start.fullmove_number,
+            start.color_to_move,
+        )?;
+        tokens.push(self.result.to_token().to_string());
+
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() > LINE_WIDTH {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        pgn.push_str(&line);
+        pgn.push('\n');
+        Ok(pgn)
+    }
+}
+
+/// Appends a `[Name "Value"]` line, escaping quotes and backslashes
+fn push_tag(pgn: &mut String, name: &str, value: &str) {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    pgn.push_str(&format!("[{} \"{}\"]\n", name, escaped));
+}
+
+/// Appends a brace comment as one token per word so it can be wrapped
+fn push_comment(tokens: &mut Vec<String>, comment: &str) {
+    let words: Vec<&str> = comment.split_whitespace().collect();
+    if words.is_empty() {
+        tokens.push("{}".to_string());
+        return;
+    }
+    let last = words.len() - 1;
+    for (i, word) in words.iter().enumerate() {
+        let mut token = word.to_string();
+        if i == 0 {
+            token.insert(0, '{');
+        }
+        if i == last {
+            token.push('}');
+        }
+        tokens.push(token);
+    }
+}
+
+/// Appends the tokens of a line of moves played from `board`
+fn push_line(
+    tokens: &mut Vec<String>,
+    moves: &[PgnMove],
+    board: &Board,
+    mut number: u32,
+    mut color: Color,
+) -> Result<(), ChessError> {
+    let mut board = board.clone();
+    // Black moves need a "N..." number at the start of a line and after comments or variations
+    let mut needs_number = true;
+    for pgn_move in moves {
+        if let Some(comment) = &pgn_move.comment_before {
+            push_comment(tokens, comment);
+            needs_number = true;
+        }
+        match color {
+            Color::White => tokens.push(format!("{}.", number)),
+            Color::Black if needs_number => tokens.push(format!("{}...", number)),
+            Color::Black => {}
+        }
+        let mv = board.resolve_move(&pgn_move.mv)?;
+        tokens.push(board.to_san(&mv)?);
+        needs_number = false;
+        for nag in &pgn_move.nags {
+            tokens.push(format!("${}", nag));
+        }
+        if let Some(comment) = &pgn_move.comment {
+            push_comment(tokens, comment);
+            needs_number = true;
+        }
+        for variation in &pgn_move.variations {
+            let start = tokens.len();
+            push_line(tokens, variation, &board, number, color)?;
+            if tokens.len() == start {
+                tokens.push("()".to_string());
+            } else {
+                tokens[start].insert(0, '(');
+                if let Some(last) = tokens.last_mut() {
+                    last.push(')');
+                }
+            }
+            needs_number = true;
+        }
+        board.execute_move(&mv);
+        if color == Color::Black {
+            number += 1;
+        }
+        color = color.reverse();
+    }
+    Ok(())
+}
@@ -1 +1,2 @@
+#[allow(clippy::module_inception)]
 pub mod piece;
@@ -1,7 +1,8 @@
 use crate::chess::board::{board::Board, coordinates::Coordinates};
+use crate::chess::error::ChessError;
 
 /// Chess piece types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PieceType {
     Pawn,
     Knight,
@@ -12,7 +13,7 @@ pub enum PieceType {
 }
 
 /// Player colors
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
     White,
     Black,
@@ -55,7 +56,7 @@ pub trait ChessPiece {
     fn coordinates(&self) -> Coordinates;
     fn change_color(&mut self, color: Color);
     fn change_coordinates(&mut self, coordinates: Coordinates);
-    fn move_to(&self, destination: Coordinates, board: &mut Board) -> Result<(), &'static str>;
+    fn move_to(&self, destination: Coordinates, board: &mut Board) -> Result<(), ChessError>;
     fn is_valid_move(
         &mut self,
         coordinates: Coordinates,
@@ -0,0 +1,86 @@
+pub mod reader;
+pub mod writer;
+
+use crate::chess::board::board::Board;
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::board::moves::{Move, MoveKind};
+use crate::chess::piece::piece::PieceType;
+
+/// Size of a book entry in bytes
+pub const ENTRY_SIZE: usize = 16;
+
+/// A single record of a Polyglot book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BookEntry {
+    /// Polyglot key of the position
+    pub key: u64,
+    /// Move in Polyglot's 16-bit encoding; see `encode_move` and `decode_move`
+    pub mv: u16,
+    /// Relative weight of the move among the entries of the position
+    pub weight: u16,
+    /// Learning data, unused by most books
+    pub learn: u32,
+}
+
+impl BookEntry {
+    /// Reads an entry from its big-endian on-disk form
+    pub fn from_bytes(bytes: [u8; ENTRY_SIZE]) -> BookEntry {
+        BookEntry {
+            key: u64::from_be_bytes(bytes[0..8].try_into().expect("8 bytes")),
+            mv: u16::from_be_bytes([bytes[8], bytes[9]]),
+            weight: u16::from_be_bytes([bytes[10], bytes[11]]),
+            learn: u32::from_be_bytes(bytes[12..16].try_into().expect("4 bytes")),
+        }
+    }
+
+    /// Converts the entry to its big-endian on-disk form
+    pub fn to_bytes(&self) -> [u8; ENTRY_SIZE] {
+        let mut bytes = [0; ENTRY_SIZE];
+        bytes[0..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.mv.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.learn.to_be_bytes());
+        bytes
+    }
+}
+
+/// Computes the Polyglot key of a position
+pub fn polyglot_key(board: &Board) -> u64 {
+    board.zobrist_hash()
+}
+
+/// Encodes a move in Polyglot's format, writing castling as the king taking its own rook.
+///
+/// Polyglot books cover standard chess, so the castling rook is taken to be on the a- or h-file.
+pub fn encode_move(mv: &Move) -> u16 {
+    let to = match mv.kind {
+        MoveKind::KingsideCastle => Coordinates::new(8, mv.from.y),
+        MoveKind::QueensideCastle => Coordinates::new(1, mv.from.y),
+        _ => mv.to,
+    };
+    let promotion = match mv.promotion {
+        None => 0,
+        Some(PieceType::Knight) => 1,
+        Some(PieceType::Bishop) => 2,
+        Some(PieceType::Rook) => 3,
+        Some(_) => 4,
+    };
+    let square = |c: Coordinates| ((c.y - 1) as u16) << 3 | (c.x - 1) as u16;
+    promotion << 12 | square(mv.from) << 6 | square(to)
+}
+
+/// Decodes a Polyglot move into the legal move it stands for on `board`
+pub fn decode_move(board: &Board, raw: u16) -> Option<Move> {
+    let square = |bits: u16| Coordinates::new((bits & 7) as i8 + 1, ((bits >> 3) & 7) as i8 + 1);
+    let mut mv = Move::new(square(raw >> 6), square(raw));
+    mv.promotion = match (raw >> 12) & 7 {
+        0 => None,
+        1 => Some(PieceType::Knight),
+        2 => Some(PieceType::Bishop),
+        3 => Some(PieceType::Rook),
+        4 => Some(PieceType::Queen),
+        _ => return None,
+    };
+    // parse_uci understands the king-takes-rook castling form
+    board.parse_uci(&mv.to_uci()).ok()
+}
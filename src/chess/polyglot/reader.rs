@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::error::ChessError;
+use crate::chess::polyglot::{decode_move, polyglot_key, BookEntry, ENTRY_SIZE};
+
+/// A Polyglot opening book, searched on disk without loading it into memory
+pub struct PolyglotBook<R: Read + Seek> {
+    reader: R,
+    len: u64,
+}
+
+impl PolyglotBook<BufReader<File>> {
+    /// Opens a `.bin` book file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ChessError> {
+        PolyglotBook::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> PolyglotBook<R> {
+    /// Creates a book over any seekable source of sorted entries
+    pub fn new(mut reader: R) -> Result<Self, ChessError> {
+        let size = reader.seek(SeekFrom::End(0))?;
+        if size % ENTRY_SIZE as u64 != 0 {
+            return Err(ChessError::InvalidBook(format!(
+                "size {} is not a multiple of {}",
+                size, ENTRY_SIZE
+            )));
+        }
+        Ok(Self {
+            reader,
+            len: size / ENTRY_SIZE as u64,
+        })
+    }
+
+    /// Gets the number of entries in the book
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Checks if the book has no entries
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads the entry at an index
+    fn read_entry(&mut self, index: u64) -> Result<BookEntry, ChessError> {
+        let mut bytes = [0; ENTRY_SIZE];
+        self.reader
+            .seek(SeekFrom::Start(index * ENTRY_SIZE as u64))?;
+        self.reader.read_exact(&mut bytes)?;
+        Ok(BookEntry::from_bytes(bytes))
+    }
+
+    /// Gets the raw entries stored for a position, in book order
+    pub fn entries(&mut self, board: &Board) -> Result<Vec<BookEntry>, ChessError> {
+        let key = polyglot_key(board);
+        // Binary search for the first entry with the key
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.read_entry(middle)?.key < key {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        let mut entries = Vec::new();
+        for index in low..self.len {
+            let entry = self.read_entry(index)?;
+            if entry.key != key {
+                break;
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// Gets the book moves for a position with their weights, skipping entries that are not legal moves
+    pub fn moves(&mut self, board: &Board) -> Result<Vec<(Move, u16)>, ChessError> {
+        Ok(self
+            .entries(board)?
+            .into_iter()
+            .filter_map(|entry| Some((decode_move(board, entry.mv)?, entry.weight)))
+            .collect())
+    }
+
+    /// Gets the book move with the highest weight, preferring the first on ties
+    pub fn best_move(&mut self, board: &Board) -> Result<Option<Move>, ChessError> {
+        let mut best: Option<(Move, u16)> = None;
+        for (mv, weight) in self.moves(board)? {
+            if best.is_none_or(|(_, best_weight)| weight > best_weight) {
+                best = Some((mv, weight));
+            }
+        }
+        Ok(best.map(|(mv, _)| mv))
+    }
+
+    /// Picks a book move with probability proportional to its weight.
+    ///
+    /// `random` is a uniformly distributed number from the caller's random source.
+    pub fn weighted_move(
+        &mut self,
+        board: &Board,
+        random: u64,
+    ) -> Result<Option<Move>, ChessError> {
+        let moves = self.moves(board)?;
+        let total: u64 = moves.iter().map(|&(_, weight)| u64::from(weight)).sum();
+        if total == 0 {
+            return Ok(None);
+        }
+        let mut pick = random % total;
+        for (mv, weight) in moves {
+            if pick < u64::from(weight) {
+                return Ok(Some(mv));
+            }
+            pick -= u64::from(weight);
+        }
+        unreachable!("pick is below the total weight")
+    }
+}
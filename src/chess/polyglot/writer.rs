@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::chess::error::ChessError;
+use crate::chess::pgn::reader::PgnReader;
+use crate::chess::pgn::{GameResult, PgnGame};
+use crate::chess::piece::piece::Color;
+use crate::chess::polyglot::{encode_move, polyglot_key, BookEntry};
+
+/// Number of games and accumulated score of a move in a position
+#[derive(Debug, Clone, Copy, Default)]
+struct MoveStats {
+    games: u32,
+    score: u64,
+}
+
+/// Games read from a PGN source by `BookBuilder::add_pgn`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PgnImport {
+    /// Games whose moves were added
+    pub added: usize,
+    /// Games left out because they could not be parsed or replayed
+    pub skipped: usize,
+}
+
+/// Accumulates moves from game collections into a Polyglot book
+#[derive(Debug, Clone)]
+pub struct BookBuilder {
+    max_ply: usize,
+    min_games: u32,
+    win_weight: u32,
+    draw_weight: u32,
+    loss_weight: u32,
+    stats: HashMap<(u64, u16), MoveStats>,
+}
+
+impl Default for BookBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BookBuilder {
+    /// Creates a builder that keeps the first 40 plies of each game and scores wins 2, draws 1 and losses 0
+    pub fn new() -> Self {
+        Self {
+            max_ply: 40,
+            min_games: 1,
+            win_weight: 2,
+            draw_weight: 1,
+            loss_weight: 0,
+            stats: HashMap::new(),
+        }
+    }
+
+    /// Sets how many plies of each game are added
+    pub fn max_ply(mut self, max_ply: usize) -> Self {
+        self.max_ply = max_ply;
+        self
+    }
+
+    /// Sets how many games must contain a move for it to enter the book
+    pub fn min_games(mut self, min_games: u32) -> Self {
+        self.min_games = min_games;
+        self
+    }
+
+    /// Sets the score a move earns from a game won, drawn or lost by the side that played it
+    pub fn result_weights(mut self, win: u32, draw: u32, loss: u32) -> Self {
+        self.win_weight = win;
+        self.draw_weight = draw;
+        self.loss_weight = loss;
+        self
+    }
+
+    /// Replays a game and counts its moves; games without a result score as draws.
+    ///
+    /// Nothing is added from a game that cannot be replayed.
+    pub fn add_game(&mut self, game: &PgnGame) -> Result<(), ChessError> {
+        let mut board = game.starting_board()?;
+        let mut played = Vec::new();
+        for pgn_move in game.moves.iter().take(self.max_ply) {
+            let score =
+                match (game.result, board.color_to_move) {
+                    (GameResult::WhiteWins, Color::White)
+                    | (GameResult::BlackWins, Color::Black) => self.win_weight,
+                    (GameResult::WhiteWins, Color::Black)
+                    | (GameResult::BlackWins, Color::White) => self.loss_weight,
+                    (GameResult::Draw, _) | (GameResult::Unknown, _) => self.draw_weight,
+                };
+            let mv = board.resolve_move(&pgn_move.mv)?;
+            played.push((polyglot_key(&board), encode_move(&mv), score));
+            board.execute_move(&mv);
+        }
+        for (key, mv, score) in played {
+            let stats = self.stats.entry((key, mv)).or_default();
+            stats.games += 1;
+            stats.score += u64::from(score);
+        }
+        Ok(())
+    }
+
+    /// Adds every game of a PGN source, skipping games that cannot be parsed or replayed, and
+    /// returns how many were added and skipped; only a failure to read the source is an error
+    pub fn add_pgn<R: Read>(&mut self, reader: R) -> Result<PgnImport, ChessError> {
+        let mut import = PgnImport::default();
+        for game in PgnReader::new(reader) {
+            let added = match game {
+                Ok(game) => self.add_game(&game).is_ok(),
+                Err(ChessError::Io(reason)) => return Err(ChessError::Io(reason)),
+                Err(_) => false,
+            };
+            if added {
+                import.added += 1;
+            } else {
+                import.skipped += 1;
+            }
+        }
+        Ok(import)
+    }
+
+    /// Gets the book entries sorted by key, then by descending weight.
+    ///
+    /// Moves played in fewer than the minimum number of games or with no score are left out,
+    /// and weights are scaled down evenly if the largest score does not fit in 16 bits.
+    pub fn entries(&self) -> Vec<BookEntry> {
+        let kept: Vec<(&(u64, u16), &MoveStats)> = self
+            .stats
+            .iter()
+            .filter(|(_, stats)| stats.games >= self.min_games && stats.score > 0)
+            .collect();
+        let max_score = kept.iter().map(|(_, stats)| stats.score).max().unwrap_or(0);
+        let max_weight = u64::from(u16::MAX);
+        let mut entries: Vec<BookEntry> = kept
+            .into_iter()
+            .map(|(&(key, mv), stats)| {
+                let weight = if max_score > max_weight {
+                    (stats.score * max_weight / max_score).max(1)
+                } else {
+                    stats.score
+                };
+                BookEntry {
+                    key,
+                    mv,
+                    weight: weight as u16,
+                    learn: 0,
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            a.key
+                .cmp(&b.key)
+                .then(b.weight.cmp(&a.weight))
+                .then(a.mv.cmp(&b.mv))
+        });
+        entries
+    }
+
+    /// Writes the book in Polyglot `.bin` format
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ChessError> {
+        for entry in self.entries() {
+            writer.write_all(&entry.to_bytes())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the book to a `.bin` file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ChessError> {
+        self.write(BufWriter::new(File::create(path)?))
+    }
+}
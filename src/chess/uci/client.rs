@@ -0,0 +1,234 @@
+use std::ffi::OsStr;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::error::ChessError;
+use crate::chess::uci::{move_to_uci, split_command, GoParams, Info, UciOption};
+
+/// The engine's final answer to a `go` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestMove {
+    /// Move to play, or `None` when the engine has no legal move
+    pub mv: Option<Move>,
+    /// Reply the engine expects, for pondering
+    pub ponder: Option<Move>,
+}
+
+/// A line of engine output during a search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchEvent {
+    /// Search progress
+    Info(Info),
+    /// End of the search
+    BestMove(BestMove),
+}
+
+/// Everything an engine reported for one search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// Final answer
+    pub best_move: BestMove,
+    /// Progress reports in the order they arrived
+    pub infos: Vec<Info>,
+}
+
+/// A UCI engine running as a child process.
+///
+/// Reads block until the engine answers; the process is stopped when the client is dropped.
+pub struct UciEngine {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    name: Option<String>,
+    author: Option<String>,
+    options: Vec<UciOption>,
+    position: Board,
+    chess960: bool,
+}
+
+impl UciEngine {
+    /// Starts the engine at `path` and performs the `uci` handshake
+    pub fn spawn<S: AsRef<OsStr>>(path: S) -> Result<UciEngine, ChessError> {
+        UciEngine::from_command(Command::new(path))
+    }
+
+    /// Starts the engine with a prepared command, for arguments or a working directory,
+    /// and performs the `uci` handshake
+    pub fn from_command(mut command: Command) -> Result<UciEngine, ChessError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let mut engine = UciEngine {
+            child,
+            stdin,
+            stdout,
+            name: None,
+            author: None,
+            options: Vec::new(),
+            position: Board::starting_position(),
+            chess960: false,
+        };
+        engine.send("uci")?;
+        loop {
+            let line = engine.read_line()?;
+            let (command, rest) = split_command(&line);
+            match command {
+                "id" => match split_command(rest) {
+                    ("name", name) => engine.name = Some(name.to_string()),
+                    ("author", author) => engine.author = Some(author.to_string()),
+                    _ => {}
+                },
+                "option" => engine.options.extend(UciOption::parse(rest)),
+                "uciok" => break,
+                _ => {}
+            }
+        }
+        Ok(engine)
+    }
+
+    /// Gets the name sent with `id name`
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the author sent with `id author`
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Gets the options the engine declared
+    pub fn options(&self) -> &[UciOption] {
+        &self.options
+    }
+
+    /// Sends `isready` and waits for `readyok`
+    pub fn is_ready(&mut self) -> Result<(), ChessError> {
+        self.send("isready")?;
+        while self.read_line()? != "readyok" {}
+        Ok(())
+    }
+
+    /// Sets an option; setting `UCI_Chess960` also switches castling moves to the king-takes-rook form
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), ChessError> {
+        if name.eq_ignore_ascii_case("UCI_Chess960") {
+            self.chess960 = value == "true";
+        }
+        self.send(&format!("setoption name {} value {}", name, value))
+    }
+
+    /// Tells the engine the next position is from a new game, and waits until it is ready
+    pub fn new_game(&mut self) -> Result<(), ChessError> {
+        self.send("ucinewgame")?;
+        self.is_ready()
+    }
+
+    /// Sends the position reached by playing `moves` from `start`
+    pub fn set_position(&mut self, start: &Board, moves: &[Move]) -> Result<(), ChessError> {
+        let mut command = format!("position fen {}", start.to_fen());
+        let mut position = start.clone();
+        if !moves.is_empty() {
+            command.push_str(" moves");
+        }
+        for mv in moves {
+            let uci = move_to_uci(&position, mv, self.chess960);
+            position.apply_move(mv)?;
+            command.push(' ');
+            command.push_str(&uci);
+        }
+        self.send(&command)?;
+        self.position = position;
+        Ok(())
+    }
+
+    /// Starts a search of the current position; read its output with `read_event`
+    pub fn start_search(&mut self, params: &GoParams) -> Result<(), ChessError> {
+        self.send(&params.to_command())
+    }
+
+    /// Asks the engine to end the search and send `bestmove`
+    pub fn stop(&mut self) -> Result<(), ChessError> {
+        self.send("stop")
+    }
+
+    /// Reads output until the next `info` or `bestmove` line
+    pub fn read_event(&mut self) -> Result<SearchEvent, ChessError> {
+        loop {
+            let line = self.read_line()?;
+            match split_command(&line) {
+                ("info", rest) => return Ok(SearchEvent::Info(Info::parse(rest, &self.position))),
+                ("bestmove", rest) => {
+                    return Ok(SearchEvent::BestMove(self.parse_best_move(rest)?))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Searches the current position and collects the engine's output until `bestmove`
+    pub fn go(&mut self, params: &GoParams) -> Result<SearchResult, ChessError> {
+        self.start_search(params)?;
+        let mut infos = Vec::new();
+        loop {
+            match self.read_event()? {
+                SearchEvent::Info(info) => infos.push(info),
+                SearchEvent::BestMove(best_move) => return Ok(SearchResult { best_move, infos }),
+            }
+        }
+    }
+
+    /// Sends `quit` and waits for the process to exit
+    pub fn quit(mut self) -> Result<(), ChessError> {
+        self.send("quit")?;
+        self.child.wait()?;
+        Ok(())
+    }
+
+    /// Parses the moves after `bestmove`
+    fn parse_best_move(&self, rest: &str) -> Result<BestMove, ChessError> {
+        let tokens: Vec<&str> = rest.split_whitespace().collect();
+        let mv = match tokens.first() {
+            None | Some(&"(none)") | Some(&"0000") => None,
+            Some(uci) => Some(self.position.parse_uci(uci)?),
+        };
+        let ponder = match (mv, tokens.get(1), tokens.get(2)) {
+            (Some(mv), Some(&"ponder"), Some(uci)) => {
+                let mut position = self.position.clone();
+                position.apply_move(&mv)?;
+                position.parse_uci(uci).ok()
+            }
+            _ => None,
+        };
+        Ok(BestMove { mv, ponder })
+    }
+
+    /// Writes a command line to the engine
+    fn send(&mut self, command: &str) -> Result<(), ChessError> {
+        writeln!(self.stdin, "{}", command)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Reads a line of engine output without its line ending
+    fn read_line(&mut self) -> Result<String, ChessError> {
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Err(ChessError::Engine("engine closed its output".to_string()));
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.send("quit");
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
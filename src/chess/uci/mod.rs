@@ -0,0 +1,376 @@
+pub mod client;
+pub mod server;
+
+use std::str::FromStr;
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::piece::piece::Color;
+
+/// Evaluation reported by an engine, from the side to move's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    /// Advantage in hundredths of a pawn
+    Centipawns(i32),
+    /// Mate in this many moves; negative when the side to move gets mated
+    Mate(i32),
+}
+
+/// Type and limits of an engine option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UciOptionKind {
+    /// On/off switch
+    Check { default: bool },
+    /// Integer in a range
+    Spin { default: i64, min: i64, max: i64 },
+    /// One of a fixed set of strings
+    Combo { default: String, vars: Vec<String> },
+    /// Action without a value
+    Button,
+    /// Free text
+    String { default: String },
+}
+
+/// An option the engine declared with `option name ... type ...`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UciOption {
+    /// Name to pass to `set_option`
+    pub name: String,
+    /// Type and limits
+    pub kind: UciOptionKind,
+}
+
+impl UciOption {
+    /// Parses the fields after `option`, returning `None` for an unknown type
+    pub fn parse(line: &str) -> Option<UciOption> {
+        let mut name = Vec::new();
+        let mut kind = "";
+        let mut default = Vec::new();
+        let mut min = None;
+        let mut max = None;
+        let mut vars: Vec<Vec<&str>> = Vec::new();
+        let mut field = "";
+        for token in line.split_whitespace() {
+            match token {
+                "option" if field.is_empty() => {}
+                "name" | "type" | "default" | "min" | "max" => field = token,
+                "var" => {
+                    field = token;
+                    vars.push(Vec::new());
+                }
+                _ => match field {
+                    "name" => name.push(token),
+                    "type" => kind = token,
+                    "default" => default.push(token),
+                    "min" => min = token.parse().ok(),
+                    "max" => max = token.parse().ok(),
+                    "var" => vars.last_mut().expect("var started").push(token),
+                    _ => {}
+                },
+            }
+        }
+        // `<empty>` stands for an empty string default
+        let default = default.join(" ").replace("<empty>", "");
+        let kind = match kind {
+            "check" => UciOptionKind::Check {
+                default: default == "true",
+            },
+            "spin" => UciOptionKind::Spin {
+                default: default.parse().ok()?,
+                min: min?,
+                max: max?,
+            },
+            "combo" => UciOptionKind::Combo {
+                default,
+                vars: vars.iter().map(|var| var.join(" ")).collect(),
+            },
+            "button" => UciOptionKind::Button,
+            "string" => UciOptionKind::String { default },
+            _ => return None,
+        };
+        Some(UciOption {
+            name: name.join(" "),
+            kind,
+        })
+    }
+
+    /// Formats the option as the `option` line an engine declares it with
+    pub fn to_command(&self) -> String {
+        let mut command = format!("option name {} type ", self.name);
+        match &self.kind {
+            UciOptionKind::Check { default } => {
+                command.push_str(&format!("check default {}", default));
+            }
+            UciOptionKind::Spin { default, min, max } => {
+                command.push_str(&format!("spin default {} min {} max {}", default, min, max));
+            }
+            UciOptionKind::Combo { default, vars } => {
+                command.push_str(&format!("combo default {}", default));
+                for var in vars {
+                    command.push_str(&format!(" var {}", var));
+                }
+            }
+            UciOptionKind::Button => command.push_str("button"),
+            UciOptionKind::String { default } if default.is_empty() => {
+                command.push_str("string default <empty>");
+            }
+            UciOptionKind::String { default } => {
+                command.push_str(&format!("string default {}", default));
+            }
+        }
+        command
+    }
+}
+
+/// Search limits of a `go` command; times are in milliseconds
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GoParams {
+    /// White's remaining clock time
+    pub wtime: Option<u64>,
+    /// Black's remaining clock time
+    pub btime: Option<u64>,
+    /// White's increment per move
+    pub winc: Option<u64>,
+    /// Black's increment per move
+    pub binc: Option<u64>,
+    /// Moves left until the next time control
+    pub movestogo: Option<u32>,
+    /// Maximum search depth in plies
+    pub depth: Option<u32>,
+    /// Maximum number of nodes to search
+    pub nodes: Option<u64>,
+    /// Exact time to search
+    pub movetime: Option<u64>,
+    /// Search until `stop`
+    pub infinite: bool,
+}
+
+impl GoParams {
+    /// Limits the search to a depth
+    pub fn depth(depth: u32) -> GoParams {
+        GoParams {
+            depth: Some(depth),
+            ..GoParams::default()
+        }
+    }
+
+    /// Limits the search to a fixed time
+    pub fn movetime(movetime: u64) -> GoParams {
+        GoParams {
+            movetime: Some(movetime),
+            ..GoParams::default()
+        }
+    }
+
+    /// Searches until `stop`
+    pub fn infinite() -> GoParams {
+        GoParams {
+            infinite: true,
+            ..GoParams::default()
+        }
+    }
+
+    /// Parses the fields after `go`, skipping any it does not know
+    pub fn parse(line: &str) -> GoParams {
+        let mut params = GoParams::default();
+        let mut tokens = line.split_whitespace().peekable();
+        if tokens.peek() == Some(&"go") {
+            tokens.next();
+        }
+        while let Some(token) = tokens.next() {
+            match token {
+                "wtime" => params.wtime = next_number(&mut tokens),
+                "btime" => params.btime = next_number(&mut tokens),
+                "winc" => params.winc = next_number(&mut tokens),
+                "binc" => params.binc = next_number(&mut tokens),
+                "movestogo" => params.movestogo = next_number(&mut tokens),
+                "depth" => params.depth = next_number(&mut tokens),
+                "nodes" => params.nodes = next_number(&mut tokens),
+                "movetime" => params.movetime = next_number(&mut tokens),
+                "infinite" => params.infinite = true,
+                _ => {}
+            }
+        }
+        params
+    }
+
+    /// Suggests how long `color` should think in milliseconds, or `None` when the search has no time limit.
+    ///
+    /// A `movetime` is used as is; otherwise the clock is shared over `movestogo` moves (30 when not
+    /// given) plus most of the increment, never spending more than half of what is left.
+    pub fn time_budget(&self, color: Color) -> Option<u64> {
+        if self.movetime.is_some() {
+            return self.movetime;
+        }
+        if self.infinite {
+            return None;
+        }
+        let (time, increment) = match color {
+            Color::White => (self.wtime?, self.winc.unwrap_or(0)),
+            Color::Black => (self.btime?, self.binc.unwrap_or(0)),
+        };
+        let moves = u64::from(self.movestogo.unwrap_or(30).max(1));
+        Some((time / moves + increment * 3 / 4).min(time / 2))
+    }
+
+    /// Formats the limits as a `go` command
+    pub fn to_command(&self) -> String {
+        let mut command = String::from("go");
+        let numbers = [
+            ("wtime", self.wtime),
+            ("btime", self.btime),
+            ("winc", self.winc),
+            ("binc", self.binc),
+            ("movestogo", self.movestogo.map(u64::from)),
+            ("depth", self.depth.map(u64::from)),
+            ("nodes", self.nodes),
+            ("movetime", self.movetime),
+        ];
+        for (name, value) in numbers {
+            if let Some(value) = value {
+                command.push_str(&format!(" {} {}", name, value));
+            }
+        }
+        if self.infinite {
+            command.push_str(" infinite");
+        }
+        command
+    }
+}
+
+/// Search progress from an `info` line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Info {
+    /// Search depth in plies
+    pub depth: Option<u32>,
+    /// Selective search depth in plies
+    pub seldepth: Option<u32>,
+    /// Time searched in milliseconds
+    pub time: Option<u64>,
+    /// Nodes searched
+    pub nodes: Option<u64>,
+    /// Nodes searched per second
+    pub nps: Option<u64>,
+    /// Rank of this line when the engine reports several
+    pub multipv: Option<u32>,
+    /// Evaluation of the line
+    pub score: Option<Score>,
+    /// Principal variation, resolved against the searched position
+    pub pv: Vec<Move>,
+    /// Free text sent with `info string`
+    pub string: Option<String>,
+}
+
+impl Info {
+    /// Parses the fields after `info`, resolving the PV against `board`.
+    ///
+    /// Unknown fields are skipped, and the PV stops at the first move that is not legal.
+    pub fn parse(line: &str, board: &Board) -> Info {
+        let mut info = Info::default();
+        let mut tokens = line.split_whitespace().peekable();
+        if tokens.peek() == Some(&"info") {
+            tokens.next();
+        }
+        while let Some(token) = tokens.next() {
+            match token {
+                "depth" => info.depth = next_number(&mut tokens),
+                "seldepth" => info.seldepth = next_number(&mut tokens),
+                "time" => info.time = next_number(&mut tokens),
+                "nodes" => info.nodes = next_number(&mut tokens),
+                "nps" => info.nps = next_number(&mut tokens),
+                "multipv" => info.multipv = next_number(&mut tokens),
+                "score" => {
+                    info.score = match (tokens.next(), next_number(&mut tokens)) {
+                        (Some("cp"), Some(value)) => Some(Score::Centipawns(value)),
+                        (Some("mate"), Some(value)) => Some(Score::Mate(value)),
+                        _ => None,
+                    };
+                }
+                "pv" => {
+                    let mut position = board.clone();
+                    while let Some(uci) = tokens.peek().copied() {
+                        match position.apply_uci(uci) {
+                            Ok(mv) => info.pv.push(mv),
+                            Err(_) => break,
+                        }
+                        tokens.next();
+                    }
+                }
+                "string" => {
+                    info.string = Some(tokens.by_ref().collect::<Vec<_>>().join(" "));
+                }
+                _ => {}
+            }
+        }
+        info
+    }
+
+    /// Formats the progress as an `info` line, writing the PV from `board` in the king-takes-rook
+    /// castling form when `chess960` is set
+    pub fn to_command(&self, board: &Board, chess960: bool) -> String {
+        let mut command = String::from("info");
+        let numbers = [
+            ("depth", self.depth.map(u64::from)),
+            ("seldepth", self.seldepth.map(u64::from)),
+            ("multipv", self.multipv.map(u64::from)),
+        ];
+        for (name, value) in numbers {
+            if let Some(value) = value {
+                command.push_str(&format!(" {} {}", name, value));
+            }
+        }
+        match self.score {
+            Some(Score::Centipawns(value)) => command.push_str(&format!(" score cp {}", value)),
+            Some(Score::Mate(value)) => command.push_str(&format!(" score mate {}", value)),
+            None => {}
+        }
+        for (name, value) in [
+            ("nodes", self.nodes),
+            ("nps", self.nps),
+            ("time", self.time),
+        ] {
+            if let Some(value) = value {
+                command.push_str(&format!(" {} {}", name, value));
+            }
+        }
+        if !self.pv.is_empty() {
+            command.push_str(" pv");
+            let mut position = board.clone();
+            for mv in &self.pv {
+                command.push(' ');
+                command.push_str(&move_to_uci(&position, mv, chess960));
+                if position.apply_move(mv).is_err() {
+                    break;
+                }
+            }
+        }
+        // Everything after `string` is text, so it goes last
+        if let Some(string) = &self.string {
+            command.push_str(&format!(" string {}", string));
+        }
+        command
+    }
+}
+
+/// Formats a move for the engine or GUI, in the king-takes-rook castling form when `chess960` is set
+pub(crate) fn move_to_uci(board: &Board, mv: &Move, chess960: bool) -> String {
+    if chess960 {
+        board.to_uci_chess960(mv)
+    } else {
+        mv.to_uci()
+    }
+}
+
+/// Parses the next token as a number
+fn next_number<'a, T: FromStr>(tokens: &mut impl Iterator<Item = &'a str>) -> Option<T> {
+    tokens.next().and_then(|value| value.parse().ok())
+}
+
+/// Splits a line into its first word and the trimmed rest
+pub(crate) fn split_command(line: &str) -> (&str, &str) {
+    let line = line.trim();
+    match line.split_once(char::is_whitespace) {
+        Some((command, rest)) => (command, rest.trim()),
+        None => (line, ""),
+    }
+}
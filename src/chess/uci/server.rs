@@ -0,0 +1,353 @@
+use std::cell::RefCell;
+use std::io::{self, BufRead, Write};
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::thread;
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::error::ChessError;
+use crate::chess::uci::{move_to_uci, split_command, GoParams, Info, UciOption};
+
+/// Move selection behind a `UciServer`
+pub trait UciPlayer {
+    /// Name sent with `id name`
+    fn name(&self) -> String;
+
+    /// Author sent with `id author`
+    fn author(&self) -> String {
+        String::new()
+    }
+
+    /// Options declared to the GUI after `id`
+    fn options(&self) -> Vec<UciOption> {
+        Vec::new()
+    }
+
+    /// Applies a `setoption` command; `value` is `None` for buttons
+    fn set_option(&mut self, _name: &str, _value: Option<&str>) {}
+
+    /// Resets any state kept between moves of a game, on `ucinewgame`
+    fn new_game(&mut self) {}
+
+    /// Chooses a move for the side to move, or `None` if there is no legal move.
+    ///
+    /// Should return soon after `control.should_stop()` turns true; `params.time_budget()` suggests
+    /// how long to think. A `go infinite` search that finishes early can block in
+    /// `control.wait_for_stop()`.
+    fn go(
+        &mut self,
+        board: &Board,
+        params: &GoParams,
+        control: &mut SearchControl<'_>,
+    ) -> Option<Move>;
+}
+
+/// Lets a `UciPlayer` notice `stop` and report progress during `go`
+pub struct SearchControl<'a> {
+    stops: &'a StopSignal,
+    handled_stops: usize,
+    /// Shared with `should_stop`, which answers `isready` while the search runs
+    output: RefCell<&'a mut dyn Write>,
+    /// Formats progress for the protocol, or `None` to keep it to itself
+    format: &'a dyn Fn(&Info) -> Option<String>,
+    ready: Option<&'a ReadyRequests>,
+}
+
+impl<'a> SearchControl<'a> {
+    /// Creates a control that stops once `stops` goes past `handled_stops`, and answers the
+    /// `isready` requests held in `ready`, if any
+    pub(crate) fn new(
+        stops: &'a StopSignal,
+        handled_stops: usize,
+        output: &'a mut dyn Write,
+        format: &'a dyn Fn(&Info) -> Option<String>,
+        ready: Option<&'a ReadyRequests>,
+    ) -> SearchControl<'a> {
+        SearchControl {
+            stops,
+            handled_stops,
+            output: RefCell::new(output),
+            format,
+            ready,
+        }
+    }
+
+    /// Checks if the GUI asked for the search to end since it started
+    pub fn should_stop(&self) -> bool {
+        self.answer_ready();
+        self.stops.raised() > self.handled_stops
+    }
+
+    /// Blocks until the GUI asks for the search to end, answering `isready` in the meantime
+    pub fn wait_for_stop(&self) {
+        let mut lock = self.stops.lock.lock().expect("stop signal lock");
+        loop {
+            self.answer_ready();
+            if self.stops.raised() > self.handled_stops {
+                return;
+            }
+            lock = self.stops.changed.wait(lock).expect("stop signal lock");
+        }
+    }
+
+    /// Sends search progress to the GUI, as an `info` line over UCI
+    pub fn report(&mut self, info: &Info) {
+        self.answer_ready();
+        if let Some(line) = (self.format)(info) {
+            let output = self.output.get_mut();
+            // A failed write shows up on the server's next write
+            let _ = writeln!(output, "{}", line);
+            let _ = output.flush();
+        }
+    }
+
+    /// Answers the `isready` requests read since the search started
+    fn answer_ready(&self) {
+        let count = self.ready.map_or(0, ReadyRequests::take);
+        if count > 0 {
+            let mut output = self.output.borrow_mut();
+            for _ in 0..count {
+                let _ = writeln!(output, "readyok");
+            }
+            let _ = output.flush();
+        }
+    }
+}
+
+/// Counts the commands that end a search, and wakes a search blocked in `wait_for_stop`
+#[derive(Debug, Default)]
+pub(crate) struct StopSignal {
+    count: AtomicUsize,
+    /// Held while the count changes, so a waiter cannot miss a wake-up
+    lock: Mutex<()>,
+    changed: Condvar,
+}
+
+impl StopSignal {
+    /// Gets the number of stops raised so far
+    pub(crate) fn raised(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    /// Counts a command that ends the running search
+    pub(crate) fn raise(&self) {
+        let _lock = self.lock.lock().expect("stop signal lock");
+        self.count.fetch_add(1, Ordering::SeqCst);
+        self.changed.notify_all();
+    }
+
+    /// Wakes a waiting search without stopping it, so it answers newly held `isready` requests
+    fn wake(&self) {
+        let _lock = self.lock.lock().expect("stop signal lock");
+        self.changed.notify_all();
+    }
+}
+
+/// `isready` requests read while a search is queued or running, held back from the command queue so
+/// they can be answered without waiting for the search to end
+#[derive(Debug, Default)]
+pub(crate) struct ReadyRequests {
+    state: Mutex<ReadyState>,
+}
+
+#[derive(Debug, Default)]
+struct ReadyState {
+    /// `go` commands read but not yet finished
+    searches: usize,
+    /// `isready` requests waiting for an answer
+    pending: usize,
+}
+
+impl ReadyRequests {
+    /// Notes a `go` command read ahead of the command loop
+    fn search_queued(&self) {
+        self.state.lock().expect("ready requests lock").searches += 1;
+    }
+
+    /// Holds back an `isready` request while a search is queued or running; returns false otherwise
+    fn hold(&self) -> bool {
+        let mut state = self.state.lock().expect("ready requests lock");
+        if state.searches > 0 {
+            state.pending += 1;
+        }
+        state.searches > 0
+    }
+
+    /// Takes the held requests once every command before them has been handled, that is while the
+    /// last queued search runs
+    fn take(&self) -> usize {
+        let mut state = self.state.lock().expect("ready requests lock");
+        if state.searches == 1 {
+            mem::take(&mut state.pending)
+        } else {
+            0
+        }
+    }
+
+    /// Notes that a search ended, and takes the held requests if it was the last one queued
+    fn search_finished(&self) -> usize {
+        let count = self.take();
+        let mut state = self.state.lock().expect("ready requests lock");
+        state.searches = state.searches.saturating_sub(1);
+        count
+    }
+}
+
+/// A UCI front end that keeps the position a GUI sets up and asks a `UciPlayer` for moves
+pub struct UciServer {
+    player: Box<dyn UciPlayer>,
+    board: Board,
+    chess960: bool,
+}
+
+impl UciServer {
+    /// Creates a server for a player, starting from the standard position
+    pub fn new(player: Box<dyn UciPlayer>) -> UciServer {
+        UciServer {
+            player,
+            board: Board::starting_position(),
+            chess960: false,
+        }
+    }
+
+    /// Gets the position set by the last `position` command
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Serves standard input and output until `quit` or the end of input
+    pub fn run_stdio(&mut self) -> Result<(), ChessError> {
+        self.run(io::BufReader::new(io::stdin()), io::stdout())
+    }
+
+    /// Serves commands from `input` until `quit` or the end of input, writing replies to `output`.
+    ///
+    /// Input is read on a separate thread so that `stop` reaches a running search, and `isready` is
+    /// answered while one runs.
+    pub fn run<R, W>(&mut self, input: R, mut output: W) -> Result<(), ChessError>
+    where
+        R: BufRead + Send + 'static,
+        W: Write,
+    {
+        // Counts `stop` and `quit` lines as soon as they are read, ahead of the command queue,
+        // so a search sees them while earlier commands are still being handled
+        let stops = Arc::new(StopSignal::default());
+        let ready = Arc::new(ReadyRequests::default());
+        let (sender, lines) = mpsc::channel();
+        let reader_stops = Arc::clone(&stops);
+        let reader_ready = Arc::clone(&ready);
+        thread::spawn(move || {
+            for line in input.lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+                match split_command(&line).0 {
+                    "stop" | "quit" => reader_stops.raise(),
+                    "go" => reader_ready.search_queued(),
+                    "isready" if reader_ready.hold() => {
+                        reader_stops.wake();
+                        continue;
+                    }
+                    _ => {}
+                }
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+            // The end of input also ends a running search
+            reader_stops.raise();
+        });
+
+        let mut handled_stops = 0;
+        for line in lines {
+            let (command, rest) = split_command(&line);
+            match command {
+                "uci" => {
+                    writeln!(output, "id name {}", self.player.name())?;
+                    let author = self.player.author();
+                    if !author.is_empty() {
+                        writeln!(output, "id author {}", author)?;
+                    }
+                    for option in self.player.options() {
+                        writeln!(output, "{}", option.to_command())?;
+                    }
+                    writeln!(output, "uciok")?;
+                }
+                "isready" => writeln!(output, "readyok")?,
+                "setoption" => self.set_option(rest),
+                "ucinewgame" => {
+                    self.board = Board::starting_position();
+                    self.player.new_game();
+                }
+                "position" => {
+                    if let Err(error) = self.set_position(rest) {
+                        writeln!(output, "info string {}", error)?;
+                    }
+                }
+                "go" => {
+                    let params = GoParams::parse(rest);
+                    let board = self.board.clone();
+                    let chess960 = self.chess960;
+                    let format = |info: &Info| Some(info.to_command(&board, chess960));
+                    let mut control = SearchControl::new(
+                        &stops,
+                        handled_stops,
+                        &mut output,
+                        &format,
+                        Some(&ready),
+                    );
+                    let best_move = self.player.go(&board, &params, &mut control);
+                    for _ in 0..ready.search_finished() {
+                        writeln!(output, "readyok")?;
+                    }
+                    match best_move {
+                        Some(mv) => {
+                            let uci = move_to_uci(&board, &mv, self.chess960);
+                            writeln!(output, "bestmove {}", uci)?;
+                        }
+                        None => writeln!(output, "bestmove (none)")?,
+                    }
+                }
+                "stop" => handled_stops += 1,
+                "quit" => break,
+                _ => {}
+            }
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Handles `setoption name <name> [value <value>]`
+    fn set_option(&mut self, rest: &str) {
+        let rest = rest.strip_prefix("name").unwrap_or(rest).trim();
+        let (name, value) = match rest.split_once(" value ") {
+            Some((name, value)) => (name.trim(), Some(value.trim())),
+            None => (rest, None),
+        };
+        if name.eq_ignore_ascii_case("UCI_Chess960") {
+            self.chess960 = value == Some("true");
+        }
+        self.player.set_option(name, value);
+    }
+
+    /// Handles `position startpos|fen <fen> [moves <move>...]`, keeping the old position on error
+    fn set_position(&mut self, rest: &str) -> Result<(), ChessError> {
+        let (setup, moves) = match rest.split_once("moves") {
+            Some((setup, moves)) => (setup.trim(), moves),
+            None => (rest.trim(), ""),
+        };
+        let mut board = match split_command(setup) {
+            ("startpos", _) => Board::starting_position(),
+            ("fen", fen) => Board::from_fen(fen)?,
+            _ => return Err(ChessError::Engine(format!("invalid position: {}", rest))),
+        };
+        for uci in moves.split_whitespace() {
+            board.apply_uci(uci)?;
+        }
+        self.board = board;
+        Ok(())
+    }
+}
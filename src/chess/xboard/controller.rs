@@ -0,0 +1,286 @@
+use std::ffi::OsStr;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::chess::board::board::Board;
+use crate::chess::board::moves::Move;
+use crate::chess::error::ChessError;
+use crate::chess::pgn::GameResult;
+use crate::chess::uci::{split_command, Info, UciOption};
+use crate::chess::xboard::{
+    format_move, parse_move, parse_option_feature, parse_thinking, Feature, MoveFormat, TimeControl,
+};
+
+/// What the engine did when it was on move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineAction {
+    /// Played a move
+    Move(Move),
+    /// Resigned the game
+    Resign,
+    /// Claimed a result, with the comment given in braces
+    Claim { result: GameResult, comment: String },
+}
+
+/// The engine's answer when it was on move, with the thinking it posted before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineReply {
+    /// Move, resignation or claim
+    pub action: EngineAction,
+    /// Thinking lines in the order they arrived
+    pub thinking: Vec<Info>,
+}
+
+/// A CECP (XBoard) engine running as a child process.
+///
+/// Reads block until the engine answers; the engine has to finish its feature list with `done=1`.
+/// The process is stopped when the controller is dropped.
+pub struct XboardEngine {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    features: Vec<Feature>,
+    position: Board,
+    chess960: bool,
+    pings: u32,
+}
+
+impl XboardEngine {
+    /// Starts the engine at `path` and negotiates features with `protover 2`
+    pub fn spawn<S: AsRef<OsStr>>(path: S) -> Result<XboardEngine, ChessError> {
+        XboardEngine::from_command(Command::new(path))
+    }
+
+    /// Starts the engine with a prepared command, for arguments or a working directory, and
+    /// negotiates features with `protover 2`
+    pub fn from_command(mut command: Command) -> Result<XboardEngine, ChessError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let mut engine = XboardEngine {
+            child,
+            stdin,
+            stdout,
+            features: Vec::new(),
+            position: Board::starting_position(),
+            chess960: false,
+            pings: 0,
+        };
+        engine.send("xboard")?;
+        engine.send("protover 2")?;
+        loop {
+            let line = engine.read_line()?;
+            let (command, rest) = split_command(&line);
+            if command != "feature" {
+                continue;
+            }
+            let mut done = false;
+            for feature in Feature::parse_line(rest) {
+                // Everything is accepted; the controller adapts to what the engine announced
+                engine.send(&format!("accepted {}", feature.name))?;
+                if feature.name == "done" {
+                    done = feature.value == "1";
+                } else {
+                    engine.features.push(feature);
+                }
+            }
+            if done {
+                break;
+            }
+        }
+        Ok(engine)
+    }
+
+    /// Gets the name announced with `feature myname`
+    pub fn name(&self) -> Option<&str> {
+        self.feature("myname")
+    }
+
+    /// Gets the value of a feature the engine announced last
+    pub fn feature(&self, name: &str) -> Option<&str> {
+        self.features
+            .iter()
+            .rev()
+            .find(|feature| feature.name == name)
+            .map(|feature| feature.value.as_str())
+    }
+
+    /// Gets the options announced with `feature option`
+    pub fn options(&self) -> Vec<UciOption> {
+        self.features
+            .iter()
+            .filter(|feature| feature.name == "option")
+            .filter_map(|feature| parse_option_feature(&feature.value))
+            .collect()
+    }
+
+    /// Gets the format the engine reads and writes moves in
+    pub fn move_format(&self) -> MoveFormat {
+        match self.feature("san") {
+            Some("1") => MoveFormat::San,
+            _ => MoveFormat::Coordinate,
+        }
+    }
+
+    /// Sets an option; buttons take no value
+    pub fn set_option(&mut self, name: &str, value: Option<&str>) -> Result<(), ChessError> {
+        match value {
+            Some(value) => self.send(&format!("option {}={}", name, value)),
+            None => self.send(&format!("option {}", name)),
+        }
+    }
+
+    /// Starts a new game from `start` in force mode, so the engine waits for moves or `go`.
+    ///
+    /// `chess960` selects `variant fischerandom`; a start other than the standard position needs the
+    /// `setboard` feature.
+    pub fn new_game(&mut self, start: &Board, chess960: bool) -> Result<(), ChessError> {
+        self.send("new")?;
+        if chess960 {
+            self.send("variant fischerandom")?;
+        }
+        self.send("force")?;
+        self.send("post")?;
+        if start.to_fen() != Board::starting_position().to_fen() {
+            if self.feature("setboard") != Some("1") {
+                return Err(ChessError::Engine(
+                    "engine does not support setboard".to_string(),
+                ));
+            }
+            self.send(&format!("setboard {}", start.to_fen()))?;
+        }
+        self.position = start.clone();
+        self.chess960 = chess960;
+        Ok(())
+    }
+
+    /// Sets the time control
+    pub fn set_level(&mut self, time_control: &TimeControl) -> Result<(), ChessError> {
+        self.send(&time_control.to_command())
+    }
+
+    /// Limits the search to a depth in plies
+    pub fn set_depth(&mut self, depth: u32) -> Result<(), ChessError> {
+        self.send(&format!("sd {}", depth))
+    }
+
+    /// Limits the search to a fixed number of seconds per move
+    pub fn set_move_time(&mut self, seconds: u64) -> Result<(), ChessError> {
+        self.send(&format!("st {}", seconds))
+    }
+
+    /// Updates both clocks, in milliseconds, before the engine moves
+    pub fn set_clocks(&mut self, engine: u64, opponent: u64) -> Result<(), ChessError> {
+        self.send(&format!("time {}", engine / 10))?;
+        self.send(&format!("otim {}", opponent / 10))
+    }
+
+    /// Sends a move played on the board; outside force mode the engine answers when it is its turn
+    pub fn send_move(&mut self, mv: &Move) -> Result<(), ChessError> {
+        let text = format_move(&self.position, mv, self.move_format(), self.chess960)?;
+        self.position.apply_move(mv)?;
+        if self.feature("usermove") == Some("1") {
+            self.send(&format!("usermove {}", text))
+        } else {
+            self.send(&text)
+        }
+    }
+
+    /// Puts the engine in force mode, where it only follows the moves it is sent
+    pub fn force(&mut self) -> Result<(), ChessError> {
+        self.send("force")
+    }
+
+    /// Lets the engine play the side to move and waits for its answer
+    pub fn go(&mut self) -> Result<EngineReply, ChessError> {
+        self.send("go")?;
+        self.read_reply()
+    }
+
+    /// Asks the engine to move at once
+    pub fn move_now(&mut self) -> Result<(), ChessError> {
+        self.send("?")
+    }
+
+    /// Reads output until the engine moves, resigns or claims a result
+    pub fn read_reply(&mut self) -> Result<EngineReply, ChessError> {
+        let mut thinking = Vec::new();
+        loop {
+            let line = self.read_line()?;
+            let (command, rest) = split_command(&line);
+            let action = match command {
+                "move" => {
+                    let mv = parse_move(&self.position, rest)?;
+                    self.position.apply_move(&mv)?;
+                    EngineAction::Move(mv)
+                }
+                "resign" => EngineAction::Resign,
+                "Illegal" | "Error" => return Err(ChessError::Engine(line)),
+                _ => match GameResult::from_token(command) {
+                    Some(result) => EngineAction::Claim {
+                        result,
+                        comment: rest.trim_matches(['{', '}']).to_string(),
+                    },
+                    None => {
+                        thinking.extend(parse_thinking(&line, &self.position));
+                        continue;
+                    }
+                },
+            };
+            return Ok(EngineReply { action, thinking });
+        }
+    }
+
+    /// Tells the engine the game is over
+    pub fn result(&mut self, result: GameResult, comment: &str) -> Result<(), ChessError> {
+        self.send(&format!("result {} {{{}}}", result.to_token(), comment))
+    }
+
+    /// Waits until the engine has handled every command sent so far, if it supports `ping`
+    pub fn ping(&mut self) -> Result<(), ChessError> {
+        if self.feature("ping") != Some("1") {
+            return Ok(());
+        }
+        self.pings += 1;
+        let pong = format!("pong {}", self.pings);
+        self.send(&format!("ping {}", self.pings))?;
+        while self.read_line()? != pong {}
+        Ok(())
+    }
+
+    /// Sends `quit` and waits for the process to exit
+    pub fn quit(mut self) -> Result<(), ChessError> {
+        self.send("quit")?;
+        self.child.wait()?;
+        Ok(())
+    }
+
+    /// Writes a command line to the engine
+    fn send(&mut self, command: &str) -> Result<(), ChessError> {
+        writeln!(self.stdin, "{}", command)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Reads a line of engine output without its line ending
+    fn read_line(&mut self) -> Result<String, ChessError> {
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Err(ChessError::Engine("engine closed its output".to_string()));
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+impl Drop for XboardEngine {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.send("quit");
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use crate::chess::board::board::Board;
+use crate::chess::error::ChessError;
+use crate::chess::piece::piece::Color;
+use crate::chess::uci::server::{SearchControl, StopSignal, UciPlayer};
+use crate::chess::uci::{split_command, GoParams, Info};
+use crate::chess::xboard::{
+    format_move, format_thinking, option_feature, parse_move, Feature, MoveFormat, TimeControl,
+};
+
+/// Commands that end a search without a move
+const INTERRUPTS: [&str; 7] = [
+    "quit", "new", "force", "result", "setboard", "undo", "remove",
+];
+
+/// A CECP front end that serves a `UciPlayer`, so one engine speaks both protocols.
+///
+/// Moves are sent in coordinate notation, with `O-O`/`O-O-O` castling in Chess960 (`variant
+/// fischerandom`), and both coordinate notation and SAN are accepted.
+pub struct XboardServer {
+    player: Box<dyn UciPlayer>,
+    board: Board,
+    /// Positions before each move played since `new` or `setboard`, for `undo`
+    history: Vec<Board>,
+    /// Side the engine plays, or `None` in force mode
+    engine_color: Option<Color>,
+    chess960: bool,
+    time_control: Option<TimeControl>,
+    /// Engine's clock from `time`, in milliseconds
+    time: Option<u64>,
+    /// Opponent's clock from `otim`, in milliseconds
+    opponent_time: Option<u64>,
+    depth: Option<u32>,
+    move_time: Option<u64>,
+    post: bool,
+}
+
+impl XboardServer {
+    /// Creates a server for a player, starting from the standard position with the engine playing Black
+    pub fn new(player: Box<dyn UciPlayer>) -> XboardServer {
+        XboardServer {
+            player,
+            board: Board::starting_position(),
+            history: Vec::new(),
+            engine_color: Some(Color::Black),
+            chess960: false,
+            time_control: None,
+            time: None,
+            opponent_time: None,
+            depth: None,
+            move_time: None,
+            post: false,
+        }
+    }
+
+    /// Gets the current position
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Serves standard input and output until `quit` or the end of input
+    pub fn run_stdio(&mut self) -> Result<(), ChessError> {
+        self.run(io::BufReader::new(io::stdin()), io::stdout())
+    }
+
+    /// Serves commands from `input` until `quit` or the end of input, writing replies to `output`.
+    ///
+    /// Input is read on a separate thread so that `?` reaches a running search; commands such as
+    /// `force` or `new` that arrive during a search also end it, and its move is dropped. Commands
+    /// read before the search started are handled after it in order.
+    pub fn run<R, W>(&mut self, input: R, mut output: W) -> Result<(), ChessError>
+    where
+        R: BufRead + Send + 'static,
+        W: Write,
+    {
+        let searching = Arc::new(AtomicBool::new(false));
+        let stops = Arc::new(StopSignal::default());
+        let interrupts = Arc::new(AtomicUsize::new(0));
+        let (sender, lines) = mpsc::channel();
+        let reader = SearchSignals {
+            searching: Arc::clone(&searching),
+            stops: Arc::clone(&stops),
+            interrupts: Arc::clone(&interrupts),
+        };
+        thread::spawn(move || {
+            for line in input.lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+                let (command, _) = split_command(&line);
+                if reader.searching.load(Ordering::SeqCst) {
+                    if INTERRUPTS.contains(&command) {
+                        reader.interrupts.fetch_add(1, Ordering::SeqCst);
+                        reader.stops.raise();
+                    } else if command == "?" {
+                        reader.stops.raise();
+                    }
+                }
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+            // The end of input also ends a running search
+            reader.stops.raise();
+        });
+        let signals = SearchSignals {
+            searching,
+            stops,
+            interrupts,
+        };
+
+        for line in lines {
+            let (command, rest) = split_command(&line);
+            let mut search = false;
+            match command {
+                "protover" => self.send_features(&mut output)?,
+                "new" => {
+                    self.board = Board::starting_position();
+                    self.history.clear();
+                    self.engine_color = Some(Color::Black);
+                    self.chess960 = false;
+                    self.time = None;
+                    self.opponent_time = None;
+                    self.depth = None;
+                    self.player.new_game();
+                }
+                "variant" => {
+                    self.chess960 = rest == "fischerandom";
+                    let value = if self.chess960 { "true" } else { "false" };
+                    self.player.set_option("UCI_Chess960", Some(value));
+                }
+                "force" | "result" => self.engine_color = None,
+                "go" => {
+                    self.engine_color = Some(self.board.color_to_move);
+                    search = true;
+                }
+                "playother" => self.engine_color = Some(self.board.color_to_move.reverse()),
+                "level" => self.time_control = TimeControl::parse(rest),
+                "st" => self.move_time = rest.parse::<u64>().ok().map(|seconds| seconds * 1000),
+                "sd" => self.depth = rest.parse().ok(),
+                "time" => self.time = rest.parse::<u64>().ok().map(|centis| centis * 10),
+                "otim" => self.opponent_time = rest.parse::<u64>().ok().map(|centis| centis * 10),
+                "post" => self.post = true,
+                "nopost" => self.post = false,
+                "ping" => writeln!(output, "pong {}", rest)?,
+                "option" => {
+                    let (name, value) = match rest.split_once('=') {
+                        Some((name, value)) => (name, Some(value)),
+                        None => (rest, None),
+                    };
+                    self.player.set_option(name, value);
+                }
+                "setboard" => match Board::from_fen(rest) {
+                    Ok(board) => {
+                        self.board = board;
+                        self.history.clear();
+                    }
+                    Err(_) => writeln!(output, "Error (illegal position): {}", line.trim())?,
+                },
+                "undo" => self.take_back(1),
+                "remove" => self.take_back(2),
+                "usermove" => search = self.user_move(rest, &mut output)?,
+                "quit" => break,
+                "xboard" | "?" | "accepted" | "rejected" | "random" | "hard" | "easy"
+                | "computer" | "name" | "rating" | "ics" | "draw" | "white" | "black" | "" => {}
+                // Protocol version 1 GUIs send moves without `usermove`
+                _ if parse_move(&self.board, &line).is_ok() => {
+                    search = self.user_move(&line, &mut output)?;
+                }
+                _ => writeln!(output, "Error (unknown command): {}", line.trim())?,
+            }
+            if search && self.engine_color == Some(self.board.color_to_move) {
+                self.think(&mut output, &signals)?;
+            }
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Announces the protocol features and the player's options in reply to `protover`
+    fn send_features<W: Write>(&self, output: &mut W) -> Result<(), ChessError> {
+        let name = self.player.name();
+        let features = [
+            Feature::new("myname", &name),
+            Feature::new("ping", "1"),
+            Feature::new("setboard", "1"),
+            Feature::new("usermove", "1"),
+            Feature::new("san", "0"),
+            Feature::new("colors", "0"),
+            Feature::new("sigint", "0"),
+            Feature::new("sigterm", "0"),
+            Feature::new("reuse", "1"),
+            Feature::new("analyze", "0"),
+            Feature::new("variants", "normal,fischerandom"),
+        ];
+        let features: Vec<String> = features.iter().map(Feature::to_command).collect();
+        writeln!(output, "feature {}", features.join(" "))?;
+        for option in self.player.options() {
+            let feature = Feature::new("option", &option_feature(&option));
+            writeln!(output, "feature {}", feature.to_command())?;
+        }
+        writeln!(output, "feature done=1")?;
+        Ok(())
+    }
+
+    /// Plays the opponent's move, returning whether it was legal
+    fn user_move<W: Write>(&mut self, text: &str, output: &mut W) -> Result<bool, ChessError> {
+        let mv = match parse_move(&self.board, text) {
+            Ok(mv) => mv,
+            Err(_) => {
+                writeln!(output, "Illegal move: {}", text.trim())?;
+                return Ok(false);
+            }
+        };
+        let before = self.board.clone();
+        self.board.apply_move(&mv)?;
+        self.history.push(before);
+        Ok(true)
+    }
+
+    /// Goes back `plies` moves, stopping at the position set by `new` or `setboard`
+    fn take_back(&mut self, plies: usize) {
+        for _ in 0..plies {
+            if let Some(board) = self.history.pop() {
+                self.board = board;
+            }
+        }
+    }
+
+    /// Asks the player for a move and plays it, unless a command that ends the search without a move
+    /// arrived meanwhile
+    fn think<W: Write>(
+        &mut self,
+        output: &mut W,
+        signals: &SearchSignals,
+    ) -> Result<(), ChessError> {
+        if let Some(result) = game_result(&self.board) {
+            writeln!(output, "{}", result)?;
+            return Ok(());
+        }
+        let board = self.board.clone();
+        let params = self.go_params();
+        let post = self.post;
+        let format = |info: &Info| post.then(|| format_thinking(info, &board));
+        signals.searching.store(true, Ordering::SeqCst);
+        let handled_stops = signals.stops.raised();
+        let handled_interrupts = signals.interrupts.load(Ordering::SeqCst);
+        let mut control = SearchControl::new(&signals.stops, handled_stops, output, &format, None);
+        let best_move = self.player.go(&board, &params, &mut control);
+        signals.searching.store(false, Ordering::SeqCst);
+        if signals.interrupts.load(Ordering::SeqCst) > handled_interrupts {
+            return Ok(());
+        }
+        let mv = match best_move {
+            Some(mv) => mv,
+            None => {
+                writeln!(output, "resign")?;
+                return Ok(());
+            }
+        };
+        let text = format_move(&board, &mv, MoveFormat::Coordinate, self.chess960)?;
+        self.board.apply_move(&mv)?;
+        self.history.push(board);
+        writeln!(output, "move {}", text)?;
+        if let Some(result) = game_result(&self.board) {
+            writeln!(output, "{}", result)?;
+        }
+        Ok(())
+    }
+
+    /// Converts the limits set with `level`, `st`, `sd`, `time` and `otim` for the side to move.
+    ///
+    /// Without `time` and `otim` both clocks are taken to hold the `level` base time.
+    fn go_params(&self) -> GoParams {
+        let mut params = GoParams {
+            depth: self.depth,
+            movetime: self.move_time,
+            ..GoParams::default()
+        };
+        let level = self.time_control;
+        let own = match self.time.or(level.map(|level| level.base)) {
+            Some(own) => own,
+            None => return params,
+        };
+        let opponent = self
+            .opponent_time
+            .or(level.map(|level| level.base))
+            .unwrap_or(own);
+        let (wtime, btime) = match self.board.color_to_move {
+            Color::White => (own, opponent),
+            Color::Black => (opponent, own),
+        };
+        params.wtime = Some(wtime);
+        params.btime = Some(btime);
+        params.winc = level.map(|level| level.increment);
+        params.binc = params.winc;
+        if let Some(level) = level.filter(|level| level.moves > 0) {
+            // Both sides have played one move fewer than the current move number
+            let played = self.board.fullmove_number.saturating_sub(1);
+            params.movestogo = Some(level.moves - played % level.moves);
+        }
+        params
+    }
+}
+
+/// Counts of the commands that end a search, shared with the thread reading input
+struct SearchSignals {
+    /// Set while the player is thinking; commands read at other times are not counted
+    searching: Arc<AtomicBool>,
+    /// `?` and interrupting commands
+    stops: Arc<StopSignal>,
+    /// Commands that end a search without a move
+    interrupts: Arc<AtomicUsize>,
+}
+
+/// Gets the result line announcing a game that ended in `board`
+fn game_result(board: &Board) -> Option<&'static str> {
+    if board.is_checkmate() {
+        Some(match board.color_to_move {
+            Color::White => "0-1 {Black mates}",
+            Color::Black => "1-0 {White mates}",
+        })
+    } else if board.is_stalemate() {
+        Some("1/2-1/2 {Stalemate}")
+    } else if board.has_insufficient_material() {
+        Some("1/2-1/2 {Insufficient material}")
+    } else {
+        None
+    }
+}
@@ -0,0 +1,302 @@
+pub mod controller;
+pub mod engine;
+
+use crate::chess::board::board::Board;
+use crate::chess::board::coordinates::Coordinates;
+use crate::chess::board::moves::{Move, MoveKind};
+use crate::chess::error::ChessError;
+use crate::chess::uci::{Info, Score, UciOption, UciOptionKind};
+
+/// Mate scores in thinking output are this plus or minus the number of moves
+const MATE_SCORE: i32 = 100000;
+
+/// How moves are written on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveFormat {
+    /// Coordinate notation such as `e2e4` and `e7e8q`, the default
+    Coordinate,
+    /// Standard Algebraic Notation, for engines that announce `feature san=1`
+    San,
+}
+
+/// Time control set with `level`; times are in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    /// Moves per period, or 0 when the base time covers the whole game
+    pub moves: u32,
+    /// Clock time at the start of each period
+    pub base: u64,
+    /// Time added after every move
+    pub increment: u64,
+}
+
+impl TimeControl {
+    /// Parses the fields after `level`: moves per period, base time as minutes or `minutes:seconds`,
+    /// and increment in seconds, which may be fractional
+    pub fn parse(line: &str) -> Option<TimeControl> {
+        let mut tokens = line.split_whitespace().peekable();
+        if tokens.peek() == Some(&"level") {
+            tokens.next();
+        }
+        let moves = tokens.next()?.parse().ok()?;
+        let base = tokens.next()?;
+        let base = match base.split_once(':') {
+            Some((minutes, seconds)) => {
+                minutes.parse::<u64>().ok()? * 60000 + seconds_to_ms(seconds)?
+            }
+            None => seconds_to_ms(base)? * 60,
+        };
+        let increment = seconds_to_ms(tokens.next()?)?;
+        Some(TimeControl {
+            moves,
+            base,
+            increment,
+        })
+    }
+
+    /// Formats the time control as a `level` command
+    pub fn to_command(&self) -> String {
+        let base = if self.base.is_multiple_of(60000) {
+            (self.base / 60000).to_string()
+        } else {
+            format!("{}:{:02}", self.base / 60000, self.base % 60000 / 1000)
+        };
+        format!(
+            "level {} {} {}",
+            self.moves,
+            base,
+            self.increment as f64 / 1000.0
+        )
+    }
+}
+
+/// A `feature` an engine announces, such as `san=1` or `myname="Fenex"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feature {
+    /// Feature name
+    pub name: String,
+    /// Value without quotes
+    pub value: String,
+}
+
+impl Feature {
+    /// Creates a feature
+    pub fn new(name: &str, value: &str) -> Feature {
+        Feature {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    /// Parses the `name=value` pairs after `feature`; quoted values may contain spaces
+    pub fn parse_line(line: &str) -> Vec<Feature> {
+        let line = line.trim();
+        let mut rest = line.strip_prefix("feature").unwrap_or(line).trim_start();
+        let mut features = Vec::new();
+        while let Some((name, after)) = rest.split_once('=') {
+            let (value, after) = match after.strip_prefix('"') {
+                Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+                None => after.split_once(char::is_whitespace).unwrap_or((after, "")),
+            };
+            features.push(Feature::new(name.trim(), value));
+            rest = after.trim_start();
+        }
+        features
+    }
+
+    /// Formats the feature as `name=value`, quoting values that are not numbers
+    pub fn to_command(&self) -> String {
+        if !self.value.is_empty() && self.value.bytes().all(|b| b.is_ascii_digit()) {
+            format!("{}={}", self.name, self.value)
+        } else {
+            format!("{}=\"{}\"", self.name, self.value)
+        }
+    }
+}
+
+/// Formats an engine option as the value of an `option` feature, such as `Hash -spin 16 1 1024`.
+///
+/// Combo choices are separated by `///`, with the default marked by a leading `*`.
+pub fn option_feature(option: &UciOption) -> String {
+    match &option.kind {
+        UciOptionKind::Check { default } => format!("{} -check {}", option.name, *default as u8),
+        UciOptionKind::Spin { default, min, max } => {
+            format!("{} -spin {} {} {}", option.name, default, min, max)
+        }
+        UciOptionKind::Combo { default, vars } => {
+            let choices: Vec<String> = vars
+                .iter()
+                .map(|var| {
+                    if var == default {
+                        format!("*{}", var)
+                    } else {
+                        var.clone()
+                    }
+                })
+                .collect();
+            format!("{} -combo {}", option.name, choices.join(" /// "))
+        }
+        UciOptionKind::Button => format!("{} -button", option.name),
+        UciOptionKind::String { default } => format!("{} -string {}", option.name, default),
+    }
+}
+
+/// Parses the value of an `option` feature, returning `None` for an unknown control.
+///
+/// Sliders become spins, files and paths become strings, and `-reset` and `-save` become buttons.
+pub fn parse_option_feature(value: &str) -> Option<UciOption> {
+    let (name, control) = value.split_once(" -")?;
+    let (control, args) = control.split_once(' ').unwrap_or((control, ""));
+    let args = args.trim();
+    let numbers: Vec<i64> = args
+        .split_whitespace()
+        .filter_map(|n| n.parse().ok())
+        .collect();
+    let kind = match control {
+        "check" => UciOptionKind::Check {
+            default: args == "1",
+        },
+        "spin" | "slider" => match numbers[..] {
+            [default, min, max] => UciOptionKind::Spin { default, min, max },
+            _ => return None,
+        },
+        "combo" => {
+            let mut default = None;
+            let mut vars = Vec::new();
+            for choice in args.split("///").map(str::trim) {
+                let var = match choice.strip_prefix('*') {
+                    Some(var) => {
+                        default = Some(var.to_string());
+                        var
+                    }
+                    None => choice,
+                };
+                vars.push(var.to_string());
+            }
+            UciOptionKind::Combo {
+                default: default.or_else(|| vars.first().cloned())?,
+                vars,
+            }
+        }
+        "button" | "reset" | "save" => UciOptionKind::Button,
+        "string" | "file" | "path" => UciOptionKind::String {
+            default: args.to_string(),
+        },
+        _ => return None,
+    };
+    Some(UciOption {
+        name: name.trim().to_string(),
+        kind,
+    })
+}
+
+/// Parses a move in coordinate notation or SAN.
+///
+/// Castling is accepted as `O-O`/`O-O-O`, king to its destination, or king onto its rook.
+pub fn parse_move(board: &Board, text: &str) -> Result<Move, ChessError> {
+    let text = text.trim();
+    if is_coordinate_move(text) {
+        board.parse_uci(text)
+    } else {
+        board.parse_san(text)
+    }
+}
+
+/// Formats a move for the wire; in Chess960 coordinate castling is written `O-O` or `O-O-O`
+pub fn format_move(
+    board: &Board,
+    mv: &Move,
+    format: MoveFormat,
+    chess960: bool,
+) -> Result<String, ChessError> {
+    Ok(match (format, mv.kind) {
+        (MoveFormat::San, _) => board.to_san(mv)?,
+        (MoveFormat::Coordinate, MoveKind::KingsideCastle) if chess960 => "O-O".to_string(),
+        (MoveFormat::Coordinate, MoveKind::QueensideCastle) if chess960 => "O-O-O".to_string(),
+        (MoveFormat::Coordinate, _) => mv.to_uci(),
+    })
+}
+
+/// Parses a thinking line (`ply score time nodes pv`, time in centiseconds) into search progress.
+///
+/// Mate scores of 100000 plus the number of moves become `Score::Mate`. Move numbers in the PV are
+/// skipped, and the PV stops at the first move that is not legal. Returns `None` when the line does
+/// not start with the four numbers.
+pub fn parse_thinking(line: &str, board: &Board) -> Option<Info> {
+    let mut tokens = line.split_whitespace();
+    let depth = tokens.next()?.trim_end_matches(['.', '&']).parse().ok()?;
+    let score: i32 = tokens.next()?.parse().ok()?;
+    let time: u64 = tokens.next()?.parse().ok()?;
+    let nodes = tokens.next()?.parse().ok()?;
+    let score = if score >= MATE_SCORE {
+        Score::Mate(score - MATE_SCORE)
+    } else if score <= -MATE_SCORE {
+        Score::Mate(score + MATE_SCORE)
+    } else {
+        Score::Centipawns(score)
+    };
+
+    let mut pv = Vec::new();
+    let mut position = board.clone();
+    for token in tokens {
+        if token.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+            continue;
+        }
+        match parse_move(&position, token).and_then(|mv| position.apply_move(&mv).map(|_| mv)) {
+            Ok(mv) => pv.push(mv),
+            Err(_) => break,
+        }
+    }
+    Some(Info {
+        depth: Some(depth),
+        time: Some(time * 10),
+        nodes: Some(nodes),
+        score: Some(score),
+        pv,
+        ..Info::default()
+    })
+}
+
+/// Formats search progress as a thinking line with the PV in SAN from `board`
+pub fn format_thinking(info: &Info, board: &Board) -> String {
+    let score = match info.score {
+        Some(Score::Centipawns(value)) => value,
+        Some(Score::Mate(moves)) if moves < 0 => moves - MATE_SCORE,
+        Some(Score::Mate(moves)) => moves + MATE_SCORE,
+        None => 0,
+    };
+    let mut line = format!(
+        "{} {} {} {}",
+        info.depth.unwrap_or(0),
+        score,
+        info.time.unwrap_or(0) / 10,
+        info.nodes.unwrap_or(0)
+    );
+    let mut position = board.clone();
+    for mv in &info.pv {
+        let san = match position.to_san(mv) {
+            Ok(san) => san,
+            Err(_) => break,
+        };
+        line.push(' ');
+        line.push_str(&san);
+        position
+            .apply_move(mv)
+            .expect("move was formatted as legal");
+    }
+    line
+}
+
+/// Checks if a move is written as two squares and an optional promotion letter
+fn is_coordinate_move(text: &str) -> bool {
+    text.is_ascii()
+        && (text.len() == 4 || text.len() == 5)
+        && Coordinates::from_notation_string(&text[0..2]).is_ok()
+        && Coordinates::from_notation_string(&text[2..4]).is_ok()
+}
+
+/// Converts a number of seconds, possibly fractional, to milliseconds
+fn seconds_to_ms(seconds: &str) -> Option<u64> {
+    let seconds: f64 = seconds.parse().ok()?;
+    (seconds >= 0.0).then(|| (seconds * 1000.0).round() as u64)
+}
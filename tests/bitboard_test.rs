@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+
+use fenex::chess::board::bitboard::{
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks, squares,
+};
+use fenex::chess::board::board::Board;
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::board::moves::Move;
+use fenex::chess::piece::piece::{Color, PieceType};
+
+const POSITIONS: [&str; 6] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+];
+
+/// Compares both generators at every node of the tree and returns the leaf count
+fn parity_perft(board: &mut Board, depth: u32) -> u64 {
+    let moves = board.generate_moves();
+    let bitboard: HashSet<Move> = moves.iter().copied().collect();
+    let mailbox: HashSet<Move> = board.generate_moves_mailbox().into_iter().collect();
+    assert_eq!(bitboard, mailbox, "{}", board.to_fen());
+    assert_eq!(bitboard.len(), moves.len(), "{}", board.to_fen());
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    let mut nodes = 0;
+    for mv in moves {
+        let undo = board.make_move(&mv).unwrap();
+        nodes += parity_perft(board, depth - 1);
+        board.unmake_move(undo);
+    }
+    nodes
+}
+
+fn square(notation: &str) -> usize {
+    Coordinates::from_notation_string(notation)
+        .unwrap()
+        .to_index()
+        .unwrap()
+}
+
+fn bitboard(notations: &[&str]) -> u64 {
+    notations.iter().fold(0, |bb, n| bb | 1 << square(n))
+}
+
+#[test]
+fn test_perft_parity_with_mailbox_generator() {
+    for (fen, expected) in POSITIONS
+        .iter()
+        .zip([8902, 97862, 2812, 9467, 62379, 89890])
+    {
+        let mut board = Board::from_fen(fen).unwrap();
+        assert_eq!(parity_perft(&mut board, 3), expected, "{}", fen);
+        assert_eq!(board.to_fen(), *fen);
+    }
+}
+
+#[test]
+fn test_bitboards_follow_moves() {
+    let mut board = Board::from_fen(POSITIONS[1]).unwrap();
+    for mv in board.generate_moves() {
+        let undo = board.make_move(&mv).unwrap();
+        let bitboards = *board.bitboards();
+        for index in 0..64 {
+            let expected = board
+                .get(Coordinates::from_index(index))
+                .map(|piece| (piece.color, piece.piece_type));
+            assert_eq!(bitboards.piece_at(index), expected, "after {}", mv.to_uci());
+        }
+        board.unmake_move(undo);
+    }
+    let fresh = Board::from_fen(POSITIONS[1]).unwrap();
+    assert_eq!(board.bitboards(), fresh.bitboards());
+}
+
+#[test]
+fn test_bitboard_sets() {
+    let board = Board::starting_position();
+    let bitboards = board.bitboards();
+    assert_eq!(bitboards.occupied(), 0xFFFF_0000_0000_FFFF);
+    assert_eq!(bitboards.color(Color::White), 0xFFFF);
+    assert_eq!(
+        bitboards.pieces_of(Color::Black, PieceType::Knight),
+        bitboard(&["b8", "g8"])
+    );
+    assert_eq!(bitboards.pieces(PieceType::King), bitboard(&["e1", "e8"]));
+    assert_eq!(
+        squares(bitboard(&["h8", "a1", "d4"])).collect::<Vec<_>>(),
+        [0, 27, 63]
+    );
+}
+
+#[test]
+fn test_attack_tables() {
+    assert_eq!(knight_attacks(square("a1")), bitboard(&["b3", "c2"]));
+    assert_eq!(king_attacks(square("h8")), bitboard(&["g8", "g7", "h7"]));
+    assert_eq!(pawn_attacks(Color::White, square("a2")), bitboard(&["b3"]));
+    assert_eq!(
+        pawn_attacks(Color::Black, square("e5")),
+        bitboard(&["d4", "f4"])
+    );
+
+    let occupied = bitboard(&["d6", "f4", "b4", "d2"]);
+    assert_eq!(
+        rook_attacks(square("d4"), occupied),
+        bitboard(&["d5", "d6", "d3", "d2", "e4", "f4", "c4", "b4"])
+    );
+    assert_eq!(
+        bishop_attacks(square("d4"), bitboard(&["f6", "b2"])),
+        bitboard(&["e5", "f6", "c3", "b2", "c5", "b6", "a7", "e3", "f2", "g1"])
+    );
+}
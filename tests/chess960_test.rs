@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use fenex::chess::board::board::{Board, STARTING_FEN};
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::board::moves::MoveKind;
+use fenex::chess::piece::piece::{Color, PieceType};
+
+/// Plays a UCI move, checks the resulting FEN, then takes it back and checks the position and hash
+fn assert_castle(fen: &str, uci: &str, kind: MoveKind, expected: &str) {
+    let mut board = Board::from_fen_strict(fen).unwrap();
+    let mv = board.parse_uci(uci).unwrap();
+    assert_eq!(mv.kind, kind, "{} in {}", uci, fen);
+    assert_eq!(board.to_uci_chess960(&mv), uci);
+    let undo = board.make_move(&mv).unwrap();
+    assert_eq!(board.to_fen(), expected);
+    assert_eq!(board.zobrist_hash(), board.compute_zobrist_hash());
+    board.unmake_move(undo);
+    assert_eq!(board, Board::from_fen_strict(fen).unwrap());
+}
+
+#[test]
+fn test_chess960_perft() {
+    let positions: [(&str, [u64; 3]); 4] = [
+        (
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+            [21, 528, 12189],
+        ),
+        (
+            "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+            [21, 807, 18002],
+        ),
+        (
+            "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+            [22, 593, 13440],
+        ),
+        (
+            "1nbbnrkr/p1p1ppp1/3p4/1p3P1p/3Pq2P/8/PPP1P1P1/QNBBNRKR w HFhf - 0 9",
+            [28, 1120, 31058],
+        ),
+    ];
+    for (fen, expected) in positions {
+        let board = Board::from_fen_strict(fen).unwrap();
+        for (depth, &nodes) in expected.iter().enumerate() {
+            let depth = depth as u32 + 1;
+            assert_eq!(board.perft(depth), nodes, "{} at depth {}", fen, depth);
+        }
+    }
+}
+
+#[test]
+fn test_chess960_castling() {
+    // King and rook swap squares
+    assert_castle(
+        "4k3/8/8/8/8/8/8/5KR1 w G - 0 1",
+        "f1g1",
+        MoveKind::KingsideCastle,
+        "4k3/8/8/8/8/8/8/5RK1 b - - 1 1",
+    );
+    // King already on its destination
+    assert_castle(
+        "4k3/8/8/8/8/8/8/6KR w H - 0 1",
+        "g1h1",
+        MoveKind::KingsideCastle,
+        "4k3/8/8/8/8/8/8/5RK1 b - - 1 1",
+    );
+    // Queenside castling moves the king towards the h-file
+    assert_castle(
+        "4k3/8/8/8/8/8/8/RK6 w A - 0 1",
+        "b1a1",
+        MoveKind::QueensideCastle,
+        "4k3/8/8/8/8/8/8/2KR4 b - - 1 1",
+    );
+    assert_castle(
+        "rk5r/8/8/8/8/8/8/4K3 b ah - 0 1",
+        "b8h8",
+        MoveKind::KingsideCastle,
+        "r4rk1/8/8/8/8/8/8/4K3 w - - 1 2",
+    );
+
+    // A plain king move to the castling square stays a king move
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/RK6 w A - 0 1").unwrap();
+    board
+        .apply_move_coords(Coordinates::new(2, 1), Coordinates::new(3, 1))
+        .unwrap();
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/R1K5 b - - 1 1");
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/RK6 w A - 0 1").unwrap();
+    assert_eq!(
+        board.to_san(&board.parse_uci("b1a1").unwrap()).unwrap(),
+        "O-O-O"
+    );
+
+    // Moving the castling rook away would expose the king on the back rank
+    let board = Board::from_fen_strict("4k3/8/8/8/8/8/8/qRK5 w B - 0 1").unwrap();
+    assert!(board.parse_san("O-O-O").is_err());
+    // Only pieces on the squares the king or rook cross block castling
+    let board = Board::from_fen_strict("4k3/8/8/8/8/8/8/N4K1R w H - 0 1").unwrap();
+    assert!(board.parse_san("O-O").is_ok());
+    let board = Board::from_fen_strict("4k3/8/8/8/8/8/8/5KNR w H - 0 1").unwrap();
+    assert!(board.parse_san("O-O").is_err());
+}
+
+#[test]
+fn test_x_fen_and_shredder_fen() {
+    let start = Board::starting_position();
+    assert_eq!(
+        start.castling_rights(),
+        [Some(8), Some(1), Some(8), Some(1)]
+    );
+    assert_eq!(
+        start.to_shredder_fen(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1"
+    );
+    let shredder = Board::from_fen_strict(&start.to_shredder_fen()).unwrap();
+    assert_eq!(shredder, start);
+    assert_eq!(shredder.to_fen(), STARTING_FEN);
+
+    // X-FEN names a rook by file only when another rook stands further out on that side
+    let fen = "1k2r2r/8/8/8/8/8/8/1K2R2R w Eh - 0 1";
+    let board = Board::from_fen_strict(fen).unwrap();
+    assert_eq!(board.castling_rights(), [Some(5), None, Some(8), None]);
+    assert_eq!(board.to_fen(), "1k2r2r/8/8/8/8/8/8/1K2R2R w Ek - 0 1");
+    assert_eq!(
+        board.to_shredder_fen(),
+        "1k2r2r/8/8/8/8/8/8/1K2R2R w Eh - 0 1"
+    );
+
+    // KQ name the outermost rooks on each side of the king
+    let board = Board::from_fen_strict("rk2r2r/8/8/8/8/8/8/RK2R2R w KQkq - 0 1").unwrap();
+    assert_eq!(
+        board.castling_rights(),
+        [Some(8), Some(1), Some(8), Some(1)]
+    );
+    assert!(Board::from_fen_strict("4k3/8/8/8/8/8/8/1K6 w A - 0 1").is_err());
+    assert!(Board::from_fen_strict("4k3/8/8/8/8/8/8/RK5R w HK - 0 1").is_err());
+}
+
+#[test]
+fn test_scharnagl_positions() {
+    assert_eq!(
+        Board::chess960_position(518).unwrap(),
+        Board::starting_position()
+    );
+    assert_eq!(
+        Board::chess960_position(0).unwrap().to_fen(),
+        "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1"
+    );
+    assert_eq!(
+        Board::chess960_position(959).unwrap().to_shredder_fen(),
+        "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w CAca - 0 1"
+    );
+    assert!(Board::chess960_position(960).is_none());
+
+    let mut back_ranks = HashSet::new();
+    for index in 0..960 {
+        let board = Board::chess960_position(index).unwrap();
+        let rank: Vec<PieceType> = (1..=8)
+            .map(|x| board.get(Coordinates::new(x, 1)).unwrap().piece_type)
+            .collect();
+        let files =
+            |piece_type| -> Vec<usize> { (0..8).filter(|&x| rank[x] == piece_type).collect() };
+        let bishops = files(PieceType::Bishop);
+        let rooks = files(PieceType::Rook);
+        let king = files(PieceType::King)[0];
+        assert_ne!(bishops[0] % 2, bishops[1] % 2);
+        assert!(rooks[0] < king && king < rooks[1]);
+        assert_eq!(
+            board.get(Coordinates::new(1, 8)).unwrap().color,
+            Color::Black
+        );
+        assert_eq!(board.generate_moves().len(), board.perft(1) as usize);
+        back_ranks.insert(rank);
+    }
+    assert_eq!(back_ranks.len(), 960);
+}
@@ -0,0 +1,147 @@
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::time::{Duration, Instant};
+
+use fenex::chess::board::board::Board;
+use fenex::chess::engine::{Engine, SearchLimits};
+use fenex::chess::uci::server::UciServer;
+use fenex::chess::uci::Score;
+
+#[test]
+fn test_search_finds_mates_and_material() {
+    let mut engine = Engine::new();
+
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let result = engine.search(&board, &SearchLimits::depth(4));
+    assert_eq!(
+        result.best_move.map(|mv| mv.to_uci()),
+        Some("a1a8".to_string())
+    );
+    assert_eq!(result.score, Score::Mate(1));
+
+    // 1. Kb6 Kb8 2. Rh8#
+    let board = Board::from_fen("k7/8/2K5/8/8/8/8/7R w - - 0 1").unwrap();
+    let result = engine.search(&board, &SearchLimits::depth(5));
+    assert_eq!(result.score, Score::Mate(2));
+    assert_eq!(result.pv.len(), 3);
+
+    // The side to move sees its own mate coming
+    let board = Board::from_fen("k7/8/1K6/8/8/8/8/7R b - - 0 1").unwrap();
+    let result = engine.search(&board, &SearchLimits::depth(4));
+    assert_eq!(result.score, Score::Mate(-1));
+
+    let board = Board::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+    let result = engine.search(&board, &SearchLimits::depth(3));
+    assert_eq!(
+        result.best_move.map(|mv| mv.to_uci()),
+        Some("d2d5".to_string())
+    );
+    assert!(matches!(result.score, Score::Centipawns(score) if score > 400));
+
+    // Quiescence sees the recapture on d5 even at depth 1
+    let board = Board::from_fen("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1").unwrap();
+    let result = engine.search(&board, &SearchLimits::depth(1));
+    assert_ne!(
+        result.best_move.map(|mv| mv.to_uci()),
+        Some("d1d5".to_string())
+    );
+
+    let mated = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+    let result = engine.search(&mated, &SearchLimits::depth(3));
+    assert_eq!((result.best_move, result.score), (None, Score::Mate(0)));
+    let stalemate = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    let result = engine.search(&stalemate, &SearchLimits::depth(3));
+    assert_eq!(
+        (result.best_move, result.score),
+        (None, Score::Centipawns(0))
+    );
+}
+
+#[test]
+fn test_search_limits_and_pv() {
+    let mut engine = Engine::with_hash_size(1);
+    let board = Board::starting_position();
+
+    let mut iterations = Vec::new();
+    let result = engine.search_with(&board, &SearchLimits::depth(3), &|| false, &mut |result| {
+        iterations.push(result.depth)
+    });
+    assert_eq!(iterations, vec![1, 2, 3]);
+    assert_eq!(result.depth, 3);
+    assert_eq!(result.best_move, result.pv.first().copied());
+    let mut position = board.clone();
+    for mv in &result.pv {
+        position.apply_move(mv).unwrap();
+    }
+
+    let result = engine.search(&board, &SearchLimits::nodes(3000));
+    assert!(result.best_move.is_some());
+    assert!(result.nodes <= 3000);
+
+    let start = Instant::now();
+    let result = engine.search(&board, &SearchLimits::time(Duration::from_millis(100)));
+    assert!(result.best_move.is_some());
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    // The first iteration always finishes
+    let result = engine.search_with(&board, &SearchLimits::default(), &|| true, &mut |_| {});
+    assert_eq!(result.depth, 1);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn test_engine_over_uci() {
+    let mut server = UciServer::new(Box::new(Engine::new()));
+    let mut output = Vec::new();
+    let commands = "uci\n\
+                    setoption name Hash value 4\n\
+                    position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1\n\
+                    go depth 3\n\
+                    ucinewgame\n\
+                    position startpos moves e2e4\n\
+                    go wtime 1000 btime 1000\n\
+                    quit\n";
+    server
+        .run(Cursor::new(commands.as_bytes().to_vec()), &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines[0].starts_with("id name Fenex "));
+    assert!(lines.contains(&"option name Hash type spin default 16 min 1 max 4096"));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("info depth 1 score mate 1 ") && line.ends_with(" pv a1a8")));
+    let best_moves: Vec<&str> = lines
+        .iter()
+        .filter(|line| line.starts_with("bestmove"))
+        .copied()
+        .collect();
+    assert_eq!(best_moves.len(), 2);
+    assert_eq!(best_moves[0], "bestmove a1a8");
+}
+
+#[test]
+fn test_engine_waits_for_stop_in_infinite_search() {
+    let (input, mut commands) = std::io::pipe().unwrap();
+    let (replies, output) = std::io::pipe().unwrap();
+    let gui = std::thread::spawn(move || {
+        let mut replies = BufReader::new(replies).lines().map(Result::unwrap);
+        writeln!(commands, "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        writeln!(commands, "go infinite depth 1").unwrap();
+        // The search is done after depth 1 but holds its move back until `stop`
+        let progress = replies.next().unwrap();
+        writeln!(commands, "isready").unwrap();
+        let ready = replies.next().unwrap();
+        writeln!(commands, "stop").unwrap();
+        let best_move = replies.next().unwrap();
+        writeln!(commands, "quit").unwrap();
+        (progress, ready, best_move)
+    });
+
+    UciServer::new(Box::new(Engine::new()))
+        .run(BufReader::new(input), output)
+        .unwrap();
+    let (progress, ready, best_move) = gui.join().unwrap();
+    assert!(progress.starts_with("info depth 1 score mate 1 "));
+    assert_eq!(ready, "readyok");
+    assert_eq!(best_move, "bestmove a1a8");
+}
@@ -0,0 +1,46 @@
+#!/bin/sh
+# Stand-in UCI engine for the client tests: fixed replies for a search from the
+# position after 1.e4, echoing the last position and option commands it received.
+position=""
+option=""
+while read -r line; do
+    case "$line" in
+        uci)
+            echo "id name Scripted Engine 1.0"
+            echo "id author Fenex Tests"
+            echo "option name Hash type spin default 16 min 1 max 1024"
+            echo "option name Ponder type check default false"
+            echo "option name Style type combo default Normal var Solid var Normal var Very Risky"
+            echo "option name Clear Hash type button"
+            echo "option name Book File type string default book.bin"
+            echo "uciok"
+            ;;
+        isready)
+            echo "readyok"
+            ;;
+        setoption*)
+            option="$line"
+            ;;
+        position*)
+            position="$line"
+            ;;
+        "go infinite")
+            echo "info string $position"
+            echo "info depth 1 score cp 15 pv e7e5"
+            ;;
+        go*)
+            echo "info string $position"
+            echo "info string $option"
+            echo "info depth 1 seldepth 2 multipv 1 score cp 20 nodes 30 nps 3000 time 10 pv e7e5 g1f3"
+            echo "info depth 1 multipv 2 score cp -5 nodes 30 pv c7c5 e1e2 a7a6"
+            echo "info depth 2 score mate -3 nodes 120 pv e7e5 g1f3 b8c6 zz99 a2a3"
+            echo "bestmove e7e5 ponder g1f3"
+            ;;
+        stop)
+            echo "bestmove (none)"
+            ;;
+        quit)
+            exit 0
+            ;;
+    esac
+done
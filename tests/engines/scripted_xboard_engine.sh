@@ -0,0 +1,61 @@
+#!/bin/sh
+# Stand-in CECP engine for the controller tests: fixed replies to a game starting 1.e4,
+# answering with an error when the level or option it was sent is not the expected one.
+level=""
+option=""
+setboard=""
+while read -r line; do
+    case "$line" in
+        "protover 2")
+            echo 'feature myname="Scripted Xboard 1.0" san=1 usermove=1 setboard=1 ping=1 done=0'
+            echo 'feature option="Hash -spin 16 1 1024" option="Style -combo Solid /// *Normal /// Very Risky"'
+            echo 'feature option="Clear Hash -button"'
+            echo 'feature done=1'
+            ;;
+        ping*)
+            echo "pong ${line#ping }"
+            ;;
+        level*)
+            level="$line"
+            ;;
+        option*)
+            option="$line"
+            ;;
+        new)
+            setboard=""
+            ;;
+        setboard*)
+            setboard="$line"
+            ;;
+        go)
+            if [ -n "$setboard" ]; then
+                echo "1/2-1/2 {$setboard}"
+            elif [ "$level" != "level 40 5 0" ]; then
+                echo "Error (unexpected level): $level"
+            else
+                echo "1 20 5 30 e5"
+                echo "2 -100003 10 120 1. ... e5 2. Nf3 Nc6"
+                echo "move e5"
+            fi
+            ;;
+        "usermove e4")
+            ;;
+        "usermove Nf3")
+            echo "1 15 3 40 Nc6"
+            echo "move Nc6"
+            ;;
+        "usermove Bb5")
+            if [ "$option" = "option Hash=64" ]; then
+                echo "resign"
+            else
+                echo "Error (unexpected option): $option"
+            fi
+            ;;
+        usermove*)
+            echo "Illegal move: ${line#usermove }"
+            ;;
+        quit)
+            exit 0
+            ;;
+    esac
+done
@@ -0,0 +1,164 @@
+use fenex::chess::board::board::Board;
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::board::moves::Move;
+use fenex::chess::epd::{Epd, EpdOperation};
+use fenex::chess::error::ChessError;
+
+const WAC_001: &str =
+    "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";";
+
+#[test]
+fn test_parse_epd_operations() {
+    let epd = Epd::parse(WAC_001).unwrap();
+    assert_eq!(
+        epd.board.to_fen(),
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1"
+    );
+    assert_eq!(epd.id(), Some("WAC.001"));
+    assert_eq!(epd.best_moves(), &[epd.board.parse_uci("g3g6").unwrap()]);
+
+    let line = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - \
+                am a3 h3; ce -15; acd 12; pv e4 e5 Nf3; c0 \"main; line\"; c7 \"x\"; \
+                hmvc 3; fmvn 7; xyz foo bar;";
+    let epd = Epd::parse(line).unwrap();
+    let board =
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let uci = |board: &Board, moves: &[&str]| -> Vec<_> {
+        moves
+            .iter()
+            .map(|mv| board.parse_uci(mv).unwrap())
+            .collect()
+    };
+    let mut after_e4 = board.clone();
+    after_e4
+        .apply_move(&board.parse_uci("e2e4").unwrap())
+        .unwrap();
+    let mut after_e5 = after_e4.clone();
+    after_e5
+        .apply_move(&after_e4.parse_uci("e7e5").unwrap())
+        .unwrap();
+    let pv = vec![
+        board.parse_uci("e2e4").unwrap(),
+        after_e4.parse_uci("e7e5").unwrap(),
+        after_e5.parse_uci("g1f3").unwrap(),
+    ];
+    assert_eq!(
+        epd.operations,
+        vec![
+            EpdOperation::AvoidMoves(uci(&board, &["a2a3", "h2h3"])),
+            EpdOperation::CentipawnEvaluation(-15),
+            EpdOperation::AnalysisDepth(12),
+            EpdOperation::PredictedVariation(pv),
+            EpdOperation::Comment(0, "main; line".to_string()),
+            EpdOperation::Comment(7, "x".to_string()),
+            EpdOperation::HalfmoveClock(3),
+            EpdOperation::FullmoveNumber(7),
+            EpdOperation::Other(
+                "xyz".to_string(),
+                vec!["foo".to_string(), "bar".to_string()]
+            ),
+        ]
+    );
+    assert_eq!(epd.board.halfmove_clock, 3);
+    assert_eq!(epd.board.fullmove_number, 7);
+    assert_eq!(epd.operation("acd"), Some(&EpdOperation::AnalysisDepth(12)));
+    assert!(epd.best_moves().is_empty());
+}
+
+#[test]
+fn test_epd_round_trip() {
+    let records = [
+        WAC_001,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - am a3 h3; ce -15; acd 12; pv e4 e5 Nf3; c0 \"main; line\"; hmvc 3; fmvn 7;",
+        "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - bm O-O O-O-O;",
+        "8/8/8/8/8/8/8/K6k w - -",
+        "8/8/8/8/8/8/8/K6k w - - sv \"Sicilian Defense\" 2 \"a;b\" \"\"; noop;",
+    ];
+    for record in records {
+        let epd = Epd::parse(record).unwrap();
+        assert_eq!(epd.to_epd().unwrap(), record);
+        assert_eq!(Epd::parse(&epd.to_epd().unwrap()).unwrap(), epd);
+    }
+    let epd = Epd::parse("8/8/8/8/8/8/8/K6k w - - sv \"Sicilian Defense\" 2;").unwrap();
+    assert_eq!(
+        epd.operation("sv"),
+        Some(&EpdOperation::Other(
+            "sv".to_string(),
+            vec!["Sicilian Defense".to_string(), "2".to_string()]
+        ))
+    );
+    // The final semicolon may be left out
+    let epd = Epd::parse("8/8/8/8/8/8/8/K6k w - - id \"last\"").unwrap();
+    assert_eq!(
+        epd.to_epd().unwrap(),
+        "8/8/8/8/8/8/8/K6k w - - id \"last\";"
+    );
+}
+
+#[test]
+fn test_to_epd_rejects_operations_that_cannot_round_trip() {
+    let board = Board::starting_position();
+    let e2e4 = board.parse_uci("e2e4").unwrap();
+    let e2e5 = Move::new(Coordinates::new(5, 2), Coordinates::new(5, 5));
+    let record = |operation| Epd {
+        board: board.clone(),
+        operations: vec![operation],
+    };
+
+    // Moves that are not legal cannot be written in SAN
+    assert_eq!(
+        record(EpdOperation::BestMoves(vec![e2e4, e2e5])).to_epd(),
+        Err(ChessError::IllegalMove("e2e5".to_string()))
+    );
+    assert!(record(EpdOperation::AvoidMoves(vec![e2e5]))
+        .to_epd()
+        .is_err());
+    // Nor can a variation that breaks off, instead of being cut short
+    assert_eq!(
+        record(EpdOperation::PredictedVariation(vec![e2e4, e2e4])).to_epd(),
+        Err(ChessError::IllegalMove("e2e4".to_string()))
+    );
+    let epd = record(EpdOperation::PredictedVariation(vec![e2e4]))
+        .to_epd()
+        .unwrap();
+    assert_eq!(
+        Epd::parse(&epd).unwrap().operations,
+        vec![EpdOperation::PredictedVariation(vec![e2e4])]
+    );
+
+    // EPD strings have no escape for a double quote
+    for operation in [
+        EpdOperation::Id("say \"hi\"".to_string()),
+        EpdOperation::Comment(0, "\"".to_string()),
+        EpdOperation::Other("sv".to_string(), vec!["a\"b".to_string()]),
+    ] {
+        assert!(matches!(
+            record(operation).to_epd(),
+            Err(ChessError::InvalidEpd(_))
+        ));
+    }
+}
+
+#[test]
+fn test_invalid_epd() {
+    assert!(matches!(
+        Epd::parse("8/8/8/8/8/8/8/K6k w -"),
+        Err(ChessError::InvalidEpd(_))
+    ));
+    assert!(matches!(
+        Epd::parse("8/8/8/8/8/8/8/K6k w - - id \"open;"),
+        Err(ChessError::InvalidEpd(_))
+    ));
+    assert!(matches!(
+        Epd::parse("8/8/8/8/8/8/8/K6k w - - acd deep;"),
+        Err(ChessError::InvalidEpd(_))
+    ));
+    assert!(matches!(
+        Epd::parse(WAC_001.replace("Qg6", "Qb8").as_str()),
+        Err(ChessError::IllegalMove(_)) | Err(ChessError::InvalidSan(_))
+    ));
+    assert!(matches!(
+        Epd::parse("8/8/8/8/8/8/8/K6k x - - id \"a\";"),
+        Err(ChessError::InvalidFen(_))
+    ));
+}
@@ -0,0 +1,67 @@
+use fenex::chess::board::board::Board;
+use fenex::chess::engine::eval::{evaluate, EvalWeights, Tapered};
+use fenex::chess::engine::{Engine, SearchLimits};
+use fenex::chess::error::ChessError;
+use fenex::chess::uci::Score;
+
+#[test]
+fn test_evaluate_terms() {
+    assert_eq!(evaluate(&Board::new()), 0);
+
+    // The score is from the side to move's point of view
+    let white = Board::from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+    let black = Board::from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1").unwrap();
+    assert!(evaluate(&white) > 800);
+    assert_eq!(evaluate(&black), -evaluate(&white));
+
+    // Mirrored positions score the same for the side to move
+    let mirrored = Board::from_fen("3qk3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+    assert_eq!(evaluate(&mirrored), evaluate(&white));
+
+    let pair = Board::from_fen("2b1k3/8/8/8/8/8/8/2B1KB2 w - - 0 1").unwrap();
+    let knight = Board::from_fen("2b1k3/8/8/8/8/8/8/2B1KN2 w - - 0 1").unwrap();
+    let weights = EvalWeights::default();
+    assert!(evaluate(&pair) - evaluate(&knight) > weights.bishop_pair.eg / 2);
+
+    let passed = Board::from_fen("4k3/8/3P4/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let blocked = Board::from_fen("4k3/2p5/3P4/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert!(evaluate(&passed) > 200);
+    assert!(evaluate(&blocked) < evaluate(&passed) - 100);
+
+    let doubled = Board::from_fen("4k3/8/8/8/8/3P4/3P4/4K3 w - - 0 1").unwrap();
+    let split = Board::from_fen("4k3/8/8/8/8/3P4/4P3/4K3 w - - 0 1").unwrap();
+    assert!(evaluate(&doubled) < evaluate(&split));
+}
+
+#[test]
+fn test_weights_text() {
+    let weights = EvalWeights::default();
+    let text = weights.to_text();
+    assert!(text.contains("material.knight 320 300\n"));
+    assert!(text.contains("pst.pawn.rank2 5 0 10 0 10 0 -20 0 -20 0 10 0 10 0 5 0\n"));
+    assert_eq!(EvalWeights::parse(&text), Ok(weights.clone()));
+
+    let tuned = EvalWeights::parse("# tuned\n\nbishop_pair 40 60\n").unwrap();
+    assert_eq!(tuned.bishop_pair, Tapered::new(40, 60));
+    assert_eq!(tuned.material, weights.material);
+
+    for text in ["bishop_pairs 40 60", "bishop_pair 40", "bishop_pair 40 x"] {
+        assert!(matches!(
+            EvalWeights::parse(text),
+            Err(ChessError::InvalidEvalWeights(_))
+        ));
+    }
+}
+
+#[test]
+fn test_engine_uses_weights() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+    let mut engine = Engine::new();
+    let mut weights = EvalWeights::default();
+    weights.material[4] = Tapered::new(100, 100);
+    engine.set_weights(weights.clone());
+    assert_eq!(engine.weights(), &weights);
+
+    let result = engine.search(&board, &SearchLimits::depth(1));
+    assert!(matches!(result.score, Score::Centipawns(score) if score < 500));
+}
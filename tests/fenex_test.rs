@@ -1,5 +1,8 @@
 use fenex::chess::board::board::Board;
 use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::board::fen::{FenError, FenErrorKind, FenField};
+use fenex::chess::board::moves::{Move, MoveKind};
+use fenex::chess::error::ChessError;
 use fenex::chess::piece::piece::{Color, PieceType};
 
 #[test]
@@ -23,7 +26,7 @@ fn test_checkmate_detection() {
 
     // Execute any queen move (Qh4# in real game)
     if let Some(&(from, to)) = queen_moves.first() {
-        board.apply_move(*from, *to).unwrap();
+        board.apply_move_coords(*from, *to).unwrap();
     }
 }
 
@@ -38,7 +41,7 @@ fn test_illegal_move() {
     let fen = "8/8/8/3P4/8/8/8/8 w - - 0 1";
     let mut board = Board::from_fen(fen).unwrap();
     // Pawn can't move backwards
-    let result = board.apply_move(Coordinates::new(4, 5), Coordinates::new(4, 4));
+    let result = board.apply_move_coords(Coordinates::new(4, 5), Coordinates::new(4, 4));
     assert!(result.is_err());
 }
 
@@ -81,9 +84,9 @@ fn test_castling_rights() {
     assert!(moves.contains(&(Coordinates::new(5, 1), Coordinates::new(3, 1))));
     // Castle kingside
     board
-        .apply_move(Coordinates::new(5, 1), Coordinates::new(7, 1))
+        .apply_move_coords(Coordinates::new(5, 1), Coordinates::new(7, 1))
         .unwrap();
-    assert!(!board.castling_rights[0]);
+    assert!(board.castling_rights()[0].is_none());
 }
 
 #[test]
@@ -94,7 +97,7 @@ fn test_en_passant() {
     assert!(moves.contains(&(Coordinates::new(6, 5), Coordinates::new(5, 6))));
     // Capture via en passant
     board
-        .apply_move(Coordinates::new(6, 5), Coordinates::new(5, 6))
+        .apply_move_coords(Coordinates::new(6, 5), Coordinates::new(5, 6))
         .unwrap();
     assert!(board.get(Coordinates::new(5, 5)).is_none());
 }
@@ -109,7 +112,7 @@ fn test_pawn_promotion() {
         .any(|&(from, to)| from == Coordinates::new(1, 7) && to == Coordinates::new(1, 8)));
     // Promote to queen
     board
-        .apply_move(Coordinates::new(1, 7), Coordinates::new(1, 8))
+        .apply_move_coords(Coordinates::new(1, 7), Coordinates::new(1, 8))
         .unwrap();
     assert_eq!(
         board.get(Coordinates::new(1, 8)).unwrap().piece_type,
@@ -196,7 +199,10 @@ fn test_invalid_promotion_piece() {
         Some(PieceType::King),
     );
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Invalid promotion piece");
+    assert_eq!(
+        result.unwrap_err(),
+        ChessError::InvalidPromotion(PieceType::King)
+    );
 }
 
 #[test]
@@ -319,7 +325,9 @@ fn test_check_escape_by_moving_king() {
         .find(|(from, _to)| from == &Coordinates::new(5, 1))
         .expect("King should have legal moves");
 
-    board.apply_move(escape_move.0, escape_move.1).unwrap();
+    board
+        .apply_move_coords(escape_move.0, escape_move.1)
+        .unwrap();
     assert!(!board.is_in_check());
 }
 
@@ -338,7 +346,9 @@ fn test_check_escape_by_blocking() {
         .find(|(_from, to)| to == &Coordinates::new(6, 1))
         .expect("Should be able to block check");
 
-    board.apply_move(blocking_move.0, blocking_move.1).unwrap();
+    board
+        .apply_move_coords(blocking_move.0, blocking_move.1)
+        .unwrap();
     assert!(!board.is_in_check());
 }
 
@@ -357,7 +367,9 @@ fn test_check_escape_by_capture() {
         .find(|(_from, to)| to == &Coordinates::new(8, 1))
         .expect("Should be able to capture attacking rook");
 
-    board.apply_move(capture_move.0, capture_move.1).unwrap();
+    board
+        .apply_move_coords(capture_move.0, capture_move.1)
+        .unwrap();
     assert!(!board.is_in_check());
 }
 
@@ -514,7 +526,7 @@ fn test_debug_enemy_check_restriction() {
 fn test_complex_position_can_give_check() {
     // Test a more complex position similar to what might occur in a real game
     let fen = "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
-    let mut board = Board::from_fen(fen).unwrap();
+    let board = Board::from_fen(fen).unwrap();
 
     println!("Testing complex position...");
     let legal_moves = board.generate_legal_moves();
@@ -526,13 +538,13 @@ fn test_complex_position_can_give_check() {
     for (from, to) in &legal_moves {
         // Simulate the move
         let mut test_board = board.clone();
-        test_board.apply_move(*from, *to).unwrap();
+        test_board.apply_move_coords(*from, *to).unwrap();
 
         // Switch perspective and check if black king is in check
-        test_board.color_to_move = match test_board.color_to_move {
+        test_board.set_color_to_move(match test_board.color_to_move() {
             Color::White => Color::Black,
             Color::Black => Color::White,
-        };
+        });
 
         if test_board.is_in_check() {
             check_giving_moves.push((*from, *to));
@@ -573,12 +585,12 @@ fn test_actual_move_execution_giving_check() {
     println!("White king on e1, White rook on e2, Black king on d8");
     println!("Black in check: {}", {
         let mut temp_board = board.clone();
-        temp_board.color_to_move = Color::Black;
+        temp_board.set_color_to_move(Color::Black);
         temp_board.is_in_check()
     });
 
     // Move rook to d2 to give check
-    let result = board.apply_move(Coordinates::new(5, 2), Coordinates::new(4, 2));
+    let result = board.apply_move_coords(Coordinates::new(5, 2), Coordinates::new(4, 2));
     match result {
         Ok(_) => {
             println!("✅ Successfully moved rook to d2");
@@ -601,7 +613,7 @@ fn test_can_give_check_without_capturing_king() {
     println!("  Black king on e8, White rook on e2, White king on f1");
     println!("  Black king in check: {}", {
         let mut temp = board.clone();
-        temp.color_to_move = Color::Black;
+        temp.set_color_to_move(Color::Black);
         temp.is_in_check()
     });
 
@@ -614,7 +626,7 @@ fn test_can_give_check_without_capturing_king() {
         .expect("Should be able to move rook to e7 to give check");
 
     println!("Moving rook from e2 to e7 to give check...");
-    board.apply_move(check_move.0, check_move.1).unwrap();
+    board.apply_move_coords(check_move.0, check_move.1).unwrap();
 
     println!("After move:");
     println!("  Current player (Black) in check: {}", board.is_in_check());
@@ -633,3 +645,581 @@ fn test_can_give_check_without_capturing_king() {
         "Black should be in check from white rook on e7"
     );
 }
+
+#[test]
+fn test_promotion_generates_four_moves() {
+    let fen = "8/P7/8/8/8/8/8/8 w - - 0 1";
+    let board = Board::from_fen(fen).unwrap();
+    let promotions: Vec<Move> = board
+        .generate_moves()
+        .into_iter()
+        .filter(|mv| mv.from == Coordinates::new(1, 7))
+        .collect();
+    assert_eq!(promotions.len(), 4);
+    for piece in [
+        PieceType::Queen,
+        PieceType::Rook,
+        PieceType::Bishop,
+        PieceType::Knight,
+    ] {
+        assert!(promotions.iter().any(|mv| mv.promotion == Some(piece)));
+    }
+    // The tuple API still lists the promotion once
+    assert_eq!(board.generate_legal_moves().len(), 1);
+}
+
+#[test]
+fn test_move_flags() {
+    let fen = "r3k2r/8/8/4pP2/8/8/4P3/R3K2R w KQkq e6 0 1";
+    let board = Board::from_fen(fen).unwrap();
+    let moves = board.generate_moves();
+    let find = |from: Coordinates, to: Coordinates| {
+        *moves
+            .iter()
+            .find(|mv| mv.from == from && mv.to == to)
+            .expect("move should be generated")
+    };
+
+    let kingside = find(Coordinates::new(5, 1), Coordinates::new(7, 1));
+    assert_eq!(kingside.kind, MoveKind::KingsideCastle);
+    assert!(kingside.is_castle());
+    let queenside = find(Coordinates::new(5, 1), Coordinates::new(3, 1));
+    assert_eq!(queenside.kind, MoveKind::QueensideCastle);
+
+    let en_passant = find(Coordinates::new(6, 5), Coordinates::new(5, 6));
+    assert!(en_passant.is_en_passant());
+    assert_eq!(en_passant.captured, Some(PieceType::Pawn));
+
+    let double_push = find(Coordinates::new(5, 2), Coordinates::new(5, 4));
+    assert_eq!(double_push.kind, MoveKind::DoublePawnPush);
+
+    let capture = find(Coordinates::new(1, 1), Coordinates::new(1, 8));
+    assert_eq!(capture.captured, Some(PieceType::Rook));
+    assert_eq!(capture.kind, MoveKind::Normal);
+}
+
+#[test]
+fn test_apply_move() {
+    let fen = "8/P7/8/8/8/8/8/8 w - - 0 1";
+    let mut board = Board::from_fen(fen).unwrap();
+    let mv = Move::with_promotion(
+        Coordinates::new(1, 7),
+        Coordinates::new(1, 8),
+        PieceType::Knight,
+    );
+    board.apply_move(&mv).unwrap();
+    assert_eq!(
+        board.get(Coordinates::new(1, 8)).unwrap().piece_type,
+        PieceType::Knight
+    );
+
+    let mut board = Board::from_fen(fen).unwrap();
+    let illegal = Move::new(Coordinates::new(1, 7), Coordinates::new(2, 8));
+    assert!(board.apply_move(&illegal).is_err());
+}
+
+#[test]
+fn test_capturing_rook_removes_castling_right() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    let mut board = Board::from_fen(fen).unwrap();
+    board
+        .apply_move_coords(Coordinates::new(8, 1), Coordinates::new(8, 8))
+        .unwrap();
+    assert_eq!(board.to_fen(), "r3k2R/8/8/8/8/8/8/R3K3 b Qq - 0 1");
+}
+
+#[test]
+fn test_make_unmake_round_trip() {
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/8/8/4pP2/8/8/4P3/R3K2R w KQkq e6 0 1",
+        "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+    ];
+    for fen in fens {
+        let mut board = Board::from_fen(fen).unwrap();
+        let original = board.clone();
+        for mv in board.generate_moves() {
+            let undo = board.make_move(&mv).unwrap();
+            assert_ne!(board.to_fen(), fen);
+            board.unmake_move(undo);
+            assert_eq!(board.to_fen(), fen);
+            assert_eq!(board, original);
+        }
+    }
+}
+
+#[test]
+fn test_unmake_move_sequence() {
+    let mut board =
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let mut history = Vec::new();
+    let mut fens = vec![board.to_fen()];
+    for (from, to) in [
+        ((5, 2), (5, 4)),
+        ((4, 7), (4, 5)),
+        ((5, 4), (4, 5)),
+        ((7, 8), (6, 6)),
+        ((6, 1), (2, 5)),
+        ((3, 7), (3, 6)),
+    ] {
+        let mv = Move::new(
+            Coordinates::new(from.0, from.1),
+            Coordinates::new(to.0, to.1),
+        );
+        history.push(board.make_move(&mv).unwrap());
+        fens.push(board.to_fen());
+    }
+    while let Some(undo) = history.pop() {
+        fens.pop();
+        board.unmake_move(undo);
+        assert_eq!(&board.to_fen(), fens.last().unwrap());
+    }
+}
+
+#[test]
+fn test_make_move_rejects_illegal_move() {
+    let mut board = Board::from_fen("8/8/8/3P4/8/8/8/8 w - - 0 1").unwrap();
+    let mv = Move::new(Coordinates::new(4, 5), Coordinates::new(4, 4));
+    assert!(board.make_move(&mv).is_err());
+}
+
+#[test]
+fn test_san_round_trip() {
+    let fens = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+    ];
+    for fen in fens {
+        let board = Board::from_fen(fen).unwrap();
+        for mv in board.generate_moves() {
+            let san = board.to_san(&mv).unwrap();
+            assert_eq!(board.parse_san(&san), Ok(mv), "{} in {}", san, fen);
+        }
+    }
+}
+
+#[test]
+fn test_san_formatting() {
+    let board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+    let san = |from: (i8, i8), to: (i8, i8)| {
+        let mv = board
+            .generate_moves()
+            .into_iter()
+            .find(|mv| {
+                mv.from == Coordinates::new(from.0, from.1) && mv.to == Coordinates::new(to.0, to.1)
+            })
+            .unwrap();
+        board.to_san(&mv).unwrap()
+    };
+    assert_eq!(san((5, 1), (7, 1)), "O-O");
+    assert_eq!(san((5, 1), (3, 1)), "O-O-O");
+    assert_eq!(san((6, 3), (8, 3)), "Qxh3");
+    assert_eq!(san((4, 5), (5, 6)), "dxe6");
+    assert_eq!(san((5, 5), (6, 7)), "Nxf7");
+    // Both the knight on c3 and the bishop on e2 can reach d1; only knights disambiguate by piece
+    assert_eq!(san((3, 3), (4, 1)), "Nd1");
+    assert_eq!(san((3, 3), (2, 1)), "Nb1");
+
+    // File disambiguation and mate suffix
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5RK w - - 0 1").unwrap();
+    let mv = board.parse_san("Rab1").unwrap();
+    assert_eq!(mv.from, Coordinates::new(1, 1));
+    assert_eq!(
+        board.to_san(&board.parse_san("Ra8").unwrap()).unwrap(),
+        "Ra8#"
+    );
+    assert_eq!(
+        board.parse_san("Rb1"),
+        Err(ChessError::AmbiguousMove("Rb1".to_string()))
+    );
+}
+
+#[test]
+fn test_san_promotion_and_check() {
+    let board = Board::from_fen("3k4/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let mv = board.parse_san("b8=N").unwrap();
+    assert_eq!(mv.promotion, Some(PieceType::Knight));
+    assert_eq!(board.to_san(&mv).unwrap(), "b8=N");
+    let mv = board.parse_san("b8Q+").unwrap();
+    assert_eq!(mv.promotion, Some(PieceType::Queen));
+    assert_eq!(board.to_san(&mv).unwrap(), "b8=Q+");
+    assert!(board.parse_san("b8").is_err());
+    assert!(board.parse_san("Nf3").is_err());
+    assert!(board.parse_san("z9").is_err());
+
+    // Moves that are not legal here are rejected instead of formatted
+    let empty = Move::new(Coordinates::new(4, 4), Coordinates::new(4, 5));
+    assert_eq!(
+        board.to_san(&empty),
+        Err(ChessError::IllegalMove("d4d5".to_string()))
+    );
+    let blocked = Move::new(Coordinates::new(5, 1), Coordinates::new(5, 3));
+    assert!(board.to_san(&blocked).is_err());
+}
+
+#[test]
+fn test_uci_moves() {
+    let mut board =
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let mv = board.apply_uci("e2e4").unwrap();
+    assert_eq!(mv.kind, MoveKind::DoublePawnPush);
+    assert_eq!(mv.to_uci(), "e2e4");
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+    );
+    assert!(board.parse_uci("e2e4").is_err());
+    assert!(board.parse_uci("e7e5x").is_err());
+    assert!(board.parse_uci("e7").is_err());
+    assert!(board.parse_uci("e7ez").is_err());
+}
+
+#[test]
+fn test_uci_promotion() {
+    let board = Board::from_fen("8/P7/8/8/8/8/8/8 w - - 0 1").unwrap();
+    let mv = board.parse_uci("a7a8n").unwrap();
+    assert_eq!(mv.promotion, Some(PieceType::Knight));
+    assert_eq!(mv.to_uci(), "a7a8n");
+    assert_eq!(
+        board.parse_uci("a7a8"),
+        Err(ChessError::MissingPromotion("a7a8".to_string()))
+    );
+    assert_eq!(
+        board.parse_uci("a7a8k"),
+        Err(ChessError::InvalidPromotion(PieceType::King))
+    );
+
+    // UCI output can be fed straight into apply_move_with_promotion
+    let mut board = board.clone();
+    board
+        .apply_move_with_promotion(mv.from, mv.to, mv.promotion)
+        .unwrap();
+    assert_eq!(board.to_fen(), "N7/8/8/8/8/8/8/8 b - - 0 1");
+}
+
+#[test]
+fn test_uci_castling_forms() {
+    let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let standard = board.parse_uci("e1g1").unwrap();
+    let king_takes_rook = board.parse_uci("e1h1").unwrap();
+    assert_eq!(standard, king_takes_rook);
+    assert_eq!(standard.kind, MoveKind::KingsideCastle);
+    assert_eq!(standard.to_uci(), "e1g1");
+    assert_eq!(board.to_uci_chess960(&standard), "e1h1");
+
+    let queenside = board.parse_uci("e1a1").unwrap();
+    assert_eq!(queenside.kind, MoveKind::QueensideCastle);
+    assert_eq!(board.to_uci_chess960(&queenside), "e1a1");
+}
+
+#[test]
+fn test_move_counters() {
+    let mut board = Board::starting_position();
+    let expected = [
+        (
+            "e4",
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        ),
+        (
+            "c5",
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+        ),
+        (
+            "Nf3",
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2",
+        ),
+        (
+            "Nc6",
+            "r1bqkbnr/pp1ppppp/2n5/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+        ),
+        (
+            "Bb5",
+            "r1bqkbnr/pp1ppppp/2n5/1Bp5/4P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3",
+        ),
+        (
+            "Nd4",
+            "r1bqkbnr/pp1ppppp/8/1Bp5/3nP3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+        ),
+        (
+            "Nxd4",
+            "r1bqkbnr/pp1ppppp/8/1Bp5/3NP3/8/PPPP1PPP/RNBQK2R b KQkq - 0 4",
+        ),
+        (
+            "cxd4",
+            "r1bqkbnr/pp1ppppp/8/1B6/3pP3/8/PPPP1PPP/RNBQK2R w KQkq - 0 5",
+        ),
+        (
+            "O-O",
+            "r1bqkbnr/pp1ppppp/8/1B6/3pP3/8/PPPP1PPP/RNBQ1RK1 b kq - 1 5",
+        ),
+    ];
+    for (san, fen) in expected {
+        let mv = board.parse_san(san).unwrap();
+        board.apply_move(&mv).unwrap();
+        assert_eq!(board.to_fen(), fen, "after {}", san);
+    }
+}
+
+#[test]
+fn test_unmake_restores_move_counters() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 17 42";
+    let mut board = Board::from_fen(fen).unwrap();
+    let undo = board.make_move(&board.parse_san("Kd8").unwrap()).unwrap();
+    assert_eq!(board.to_fen(), "r2k3r/8/8/8/8/8/8/R3K2R w KQ - 18 43");
+    board.unmake_move(undo);
+    assert_eq!(board.to_fen(), fen);
+}
+
+#[test]
+fn test_fen_syntax_errors_do_not_panic() {
+    for fen in [
+        "rnbqkbnrr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR/8 w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq 33 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq é3 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1",
+    ] {
+        assert!(Board::from_fen(fen).is_err(), "{}", fen);
+        assert!(Board::from_fen_strict(fen).is_err(), "{}", fen);
+    }
+}
+
+#[test]
+fn test_strict_fen_errors() {
+    let error = |fen: &str| match Board::from_fen_strict(fen) {
+        Err(ChessError::InvalidFen(error)) => error,
+        other => panic!("expected a FEN error, got {:?}", other),
+    };
+
+    assert_eq!(
+        error("rnbqkbnrr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        FenError {
+            field: FenField::PiecePlacement,
+            offset: 8,
+            kind: FenErrorKind::RankTooLong,
+        }
+    );
+    assert_eq!(
+        error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx - 0 1"),
+        FenError {
+            field: FenField::CastlingRights,
+            offset: 49,
+            kind: FenErrorKind::InvalidCharacter('x'),
+        }
+    );
+    assert_eq!(
+        error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -").kind,
+        FenErrorKind::MissingField
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4KK2 w - - 0 1").kind,
+        FenErrorKind::InvalidKingCount(Color::White)
+    );
+    assert_eq!(
+        error("P3k3/8/8/8/8/8/8/4K3 w - - 0 1").kind,
+        FenErrorKind::PawnOnBackRank
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w K - 0 1"),
+        FenError {
+            field: FenField::CastlingRights,
+            offset: 22,
+            kind: FenErrorKind::InvalidCastlingRight,
+        }
+    );
+    assert_eq!(
+        error("4k3/8/8/8/4P3/8/8/4K3 b - e4 0 1").kind,
+        FenErrorKind::ImpossibleEnPassant
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 b - e3 0 1").kind,
+        FenErrorKind::ImpossibleEnPassant
+    );
+    assert_eq!(
+        error("k3r3/8/8/8/8/8/8/4K3 b - - 0 1").kind,
+        FenErrorKind::OpponentInCheck
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - 0 0").kind,
+        FenErrorKind::InvalidNumber
+    );
+    let message = error("4k3/8/8/8/8/8/8/4K3 w - - x 1").to_string();
+    assert!(message.contains("halfmove clock"), "{}", message);
+
+    let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2";
+    assert_eq!(Board::from_fen_strict(fen).unwrap().to_fen(), fen);
+}
+
+#[test]
+fn test_lenient_fen_repairs() {
+    let board = Board::from_fen_lenient("r3k3/8/8/8/8/8/8/4K2R w KQkq e6").unwrap();
+    assert_eq!(board.to_fen(), "r3k3/8/8/8/8/8/8/4K2R w Kq - 0 1");
+
+    let board = Board::from_fen_lenient("4k3/8/8/8/8/8/8/4K3 w qkQK - 3 0").unwrap();
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K3 w - - 3 1");
+
+    // Problems that cannot be repaired are still rejected
+    assert!(Board::from_fen_lenient("8/8/8/8/8/8/8/4K3 w - - 0 1").is_err());
+}
+
+#[test]
+fn test_errors_carry_context() {
+    let mut board = Board::starting_position();
+    let error = board
+        .apply_move_coords(Coordinates::new(5, 2), Coordinates::new(5, 5))
+        .unwrap_err();
+    assert_eq!(error, ChessError::IllegalMove("e2e5".to_string()));
+    assert_eq!(error.to_string(), "Illegal move: e2e5");
+
+    assert_eq!(
+        board.parse_san("Nf6"),
+        Err(ChessError::IllegalMove("Nf6".to_string()))
+    );
+    assert_eq!(
+        board.parse_san("Qz9"),
+        Err(ChessError::InvalidSan("Qz9".to_string()))
+    );
+    assert_eq!(
+        board.parse_uci("e2e4x"),
+        Err(ChessError::InvalidUci("e2e4x".to_string()))
+    );
+    assert_eq!(
+        Coordinates::from_string("9,1"),
+        Err(ChessError::InvalidCoordinates("9,1".to_string()))
+    );
+    assert_eq!(
+        Coordinates::from_notation_string("i9"),
+        Err(ChessError::InvalidNotation("i9".to_string()))
+    );
+    // Multibyte characters are counted as characters, not bytes
+    for notation in ["é", "é1", "eé", "e1é"] {
+        assert_eq!(
+            Coordinates::from_notation_string(notation),
+            Err(ChessError::InvalidNotation(notation.to_string()))
+        );
+    }
+
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 x - - 0 1").unwrap_err();
+    match &error {
+        ChessError::InvalidFen(fen_error) => assert_eq!(fen_error.field, FenField::ActiveColor),
+        other => panic!("expected a FEN error, got {:?}", other),
+    }
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn test_square_attack_queries() {
+    let board = Board::from_fen("4k3/8/8/3q4/8/2N5/3R4/4K3 w - - 0 1").unwrap();
+    let square = |s: &str| Coordinates::from_notation_string(s).unwrap();
+
+    assert_eq!(
+        board.attackers_to(square("d5"), Color::White),
+        vec![square("d2"), square("c3")]
+    );
+    assert!(board.is_square_attacked(square("g2"), Color::Black));
+    assert!(board.is_square_attacked(square("d2"), Color::Black));
+    assert!(!board.is_square_attacked(square("f1"), Color::Black));
+    assert!(board.attackers_to(square("e2"), Color::Black).is_empty());
+    assert!(!board.is_square_attacked(Coordinates::new(9, 1), Color::Black));
+
+    // The rook on f2 covers f1, so White cannot castle although the king is not in check
+    let board = Board::from_fen("4k3/8/8/8/8/8/5r2/4K2R w K - 0 1").unwrap();
+    assert!(!board.is_in_check());
+    assert!(board.is_square_attacked(square("f1"), Color::Black));
+    assert!(board.parse_san("O-O").is_err());
+}
+
+#[test]
+fn test_checkers_and_pins() {
+    let square = |s: &str| Coordinates::from_notation_string(s).unwrap();
+
+    let board = Board::from_fen("4k3/8/8/8/1b6/8/8/r3K3 w - - 0 1").unwrap();
+    assert_eq!(board.checkers(), vec![square("a1"), square("b4")]);
+    assert!(Board::starting_position().checkers().is_empty());
+
+    let board = Board::from_fen("k3r3/8/8/8/1b6/4R3/3N4/4K3 w - - 0 1").unwrap();
+    let pins = board.pinned_pieces(Color::White);
+    assert_eq!(pins.len(), 2);
+    assert_eq!(pins[0].pinned, square("d2"));
+    assert_eq!(pins[0].pinner, square("b4"));
+    assert_eq!(pins[0].ray, vec![square("d2"), square("c3"), square("b4")]);
+    assert_eq!(pins[1].pinned, square("e3"));
+    assert_eq!(pins[1].pinner, square("e8"));
+    assert_eq!(pins[1].ray.first(), Some(&square("e2")));
+    assert_eq!(pins[1].ray.last(), Some(&square("e8")));
+    assert_eq!(pins[1].ray.len(), 7);
+    assert!(board.pinned_pieces(Color::Black).is_empty());
+
+    // Pinned pieces may only move along the pin ray
+    for mv in board.generate_moves() {
+        assert_ne!(mv.from, square("d2"));
+        if mv.from == square("e3") {
+            assert!(pins[1].ray.contains(&mv.to));
+        }
+    }
+}
+
+#[test]
+fn test_discovered_check() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/4N3/4R1K1 w - - 0 1").unwrap();
+    assert!(board.is_discovered_check(&board.parse_san("Nc3").unwrap()));
+    assert!(!board.is_discovered_check(&board.parse_san("Rd1").unwrap()));
+    assert!(!board.is_discovered_check(&board.parse_san("Kf2").unwrap()));
+
+    // Removing the captured pawn opens the fifth rank
+    let board = Board::from_fen("8/8/8/k1pP3R/8/8/8/4K3 w - c6 0 2").unwrap();
+    assert!(board.is_discovered_check(&board.parse_san("dxc6").unwrap()));
+    assert!(!board.is_discovered_check(&board.parse_san("d6").unwrap()));
+}
+
+#[test]
+fn test_insufficient_material() {
+    let dead = [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/4KN2 w - - 0 1",
+        "4kb2/8/8/8/8/8/8/4K3 w - - 0 1",
+        // Bishops all on dark squares
+        "4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/B1B1K3 b - - 0 1",
+    ];
+    for fen in dead {
+        let board = Board::from_fen(fen).unwrap();
+        assert!(board.has_insufficient_material(), "{}", fen);
+    }
+    let alive = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        // Opposite-colored bishops
+        "4kb2/8/8/8/8/8/8/3BK3 w - - 0 1",
+        "4kn2/8/8/8/8/8/8/4KN2 w - - 0 1",
+        "4kn2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+    ];
+    for fen in alive {
+        let board = Board::from_fen(fen).unwrap();
+        assert!(!board.has_insufficient_material(), "{}", fen);
+    }
+}
+
+#[test]
+fn test_can_possibly_win() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/4KQ2 w - - 0 1").unwrap();
+    assert!(board.can_possibly_win(Color::White));
+    assert!(!board.can_possibly_win(Color::Black));
+
+    // A knight can mate a king hemmed in by its own rook, but not by its own queen
+    let board = Board::from_fen("4kr2/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+    assert!(board.can_possibly_win(Color::White));
+    let board = Board::from_fen("4kq2/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+    assert!(!board.can_possibly_win(Color::White));
+
+    // Two knights or a bishop and knight can mate a bare king
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/3NKN2 w - - 0 1").unwrap();
+    assert!(board.can_possibly_win(Color::White));
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/3BKN2 w - - 0 1").unwrap();
+    assert!(board.can_possibly_win(Color::White));
+}
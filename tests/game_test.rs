@@ -0,0 +1,145 @@
+use fenex::chess::board::board::Board;
+use fenex::chess::board::moves::MoveKind;
+use fenex::chess::error::ChessError;
+use fenex::chess::game::{Game, Outcome};
+use fenex::chess::pgn::GameResult;
+use fenex::chess::piece::piece::Color;
+
+#[test]
+fn test_checkmate_outcome() {
+    let mut game = Game::new();
+    for san in ["f3", "e5", "g4", "Qh4#"] {
+        game.play_san(san).unwrap();
+    }
+    assert_eq!(
+        game.outcome(),
+        Some(Outcome::Checkmate {
+            winner: Color::Black
+        })
+    );
+    assert_eq!(game.moves().len(), 4);
+    assert!(game.play_san("Kf2").is_err());
+    assert_eq!(game.to_pgn().unwrap().result, GameResult::BlackWins);
+}
+
+#[test]
+fn test_stalemate_outcome() {
+    let mut game = Game::from_board(Board::from_fen("7k/8/6K1/8/8/8/8/5Q2 w - - 0 1").unwrap());
+    game.play_san("Qf7").unwrap();
+    assert_eq!(game.outcome(), Some(Outcome::Stalemate));
+    assert!(game.outcome().unwrap().is_draw());
+}
+
+#[test]
+fn test_repetition_claims() {
+    let mut game = Game::new();
+    let shuffle = ["Nf3", "Nf6", "Ng1", "Ng8"];
+    for san in shuffle {
+        game.play_san(san).unwrap();
+    }
+    assert_eq!(game.repetition_count(), 2);
+    assert_eq!(game.claim_draw(), Err(ChessError::NoDrawClaim));
+
+    // Claiming with the move that repeats the position a third time
+    for san in &shuffle[..3] {
+        game.play_san(san).unwrap();
+    }
+    let mv = game.board().parse_san("Ng8").unwrap();
+    assert_eq!(
+        game.claim_draw_with_move(&mv),
+        Ok(Outcome::ThreefoldRepetition)
+    );
+    assert!(game.is_over());
+
+    // Without a claim the game continues until the fifth occurrence
+    let mut game = Game::new();
+    for _ in 0..4 {
+        assert!(!game.is_over());
+        for san in shuffle {
+            game.play_san(san).unwrap();
+        }
+    }
+    assert_eq!(game.outcome(), Some(Outcome::FivefoldRepetition));
+}
+
+#[test]
+fn test_move_rules() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/4R3/4K3 w - - 100 80").unwrap();
+    let mut game = Game::from_board(board);
+    assert!(game.can_claim_fifty_move_rule());
+    assert_eq!(game.claim_draw(), Ok(Outcome::FiftyMoveRule));
+
+    let board = Board::from_fen("4k3/8/8/8/8/8/4R3/4K3 w - - 150 100").unwrap();
+    let game = Game::from_board(board);
+    assert_eq!(game.outcome(), Some(Outcome::SeventyFiveMoveRule));
+}
+
+#[test]
+fn test_insufficient_material_and_player_decisions() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+    assert_eq!(
+        Game::from_board(board).outcome(),
+        Some(Outcome::InsufficientMaterial)
+    );
+
+    let mut game = Game::new();
+    assert_eq!(
+        game.resign(Color::White),
+        Ok(Outcome::Resignation {
+            winner: Color::Black
+        })
+    );
+    assert_eq!(game.agree_draw(), Err(ChessError::GameOver));
+
+    let mut game = Game::new();
+    assert_eq!(
+        game.flag(Color::Black),
+        Ok(Outcome::Timeout {
+            winner: Some(Color::White)
+        })
+    );
+
+    // Running out of time is a draw when the opponent cannot checkmate
+    let board = Board::from_fen("4kq2/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+    let mut game = Game::from_board(board);
+    assert_eq!(
+        game.flag(Color::Black),
+        Ok(Outcome::Timeout { winner: None })
+    );
+    assert_eq!(game.outcome().unwrap().result(), GameResult::Draw);
+
+    let mut game = Game::new();
+    assert_eq!(game.agree_draw(), Ok(Outcome::DrawByAgreement));
+    assert_eq!(game.outcome().unwrap().result(), GameResult::Draw);
+}
+
+#[test]
+fn test_fifty_move_claim_after_quiet_move() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/4R3/4K3 w - - 99 80").unwrap();
+    let mut game = Game::from_board(board);
+    assert!(!game.can_claim_fifty_move_rule());
+    let mv = game.board().parse_san("Ra2").unwrap();
+    assert_eq!(game.claim_draw_with_move(&mv), Ok(Outcome::FiftyMoveRule));
+    assert_eq!(game.board().halfmove_clock, 100);
+}
+
+#[test]
+fn test_chess960_castling() {
+    // The king already stands next to its castling square, so O-O and Kg1 share their squares
+    let board = Board::from_fen("k7/8/8/8/8/8/8/5K1R w H - 0 1").unwrap();
+    let mut game = Game::from_board(board.clone());
+    let mv = game.play_san("O-O").unwrap();
+    assert_eq!(game.moves()[0].kind, MoveKind::KingsideCastle);
+    assert_eq!(
+        game.board().to_fen().split(' ').next(),
+        Some("k7/8/8/8/8/8/8/5RK1")
+    );
+
+    let mut game = Game::from_board(board);
+    game.play_move(&mv).unwrap();
+    assert_eq!(game.moves(), &[mv]);
+    assert_eq!(
+        game.board().to_fen().split(' ').next(),
+        Some("k7/8/8/8/8/8/8/5RK1")
+    );
+}
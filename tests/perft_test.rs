@@ -0,0 +1,75 @@
+use fenex::chess::board::board::Board;
+
+/// Checks node counts for each depth, starting at depth 1
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let board = Board::from_fen(fen).unwrap();
+    for (depth, &nodes) in expected.iter().enumerate() {
+        let depth = depth as u32 + 1;
+        assert_eq!(board.perft(depth), nodes, "{} at depth {}", fen, depth);
+    }
+}
+
+#[test]
+fn test_perft_starting_position() {
+    assert_perft(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        &[20, 400, 8902],
+    );
+}
+
+#[test]
+fn test_perft_kiwipete() {
+    assert_perft(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[48, 2039, 97862],
+    );
+}
+
+#[test]
+fn test_perft_position_3() {
+    assert_perft(
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        &[14, 191, 2812, 43238],
+    );
+}
+
+#[test]
+fn test_perft_position_4() {
+    assert_perft(
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        &[6, 264, 9467],
+    );
+    // Mirrored position
+    assert_perft(
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        &[6, 264, 9467],
+    );
+}
+
+#[test]
+fn test_perft_position_5() {
+    assert_perft(
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        &[44, 1486, 62379],
+    );
+}
+
+#[test]
+fn test_perft_position_6() {
+    assert_perft(
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        &[46, 2079, 89890],
+    );
+}
+
+#[test]
+fn test_perft_divide() {
+    let board = Board::starting_position();
+    let divide = board.perft_divide(3);
+    assert_eq!(divide.len(), 20);
+    assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 8902);
+    let e2e4 = divide.iter().find(|(mv, _)| mv.to_uci() == "e2e4").unwrap();
+    assert_eq!(e2e4.1, 600);
+    assert!(board.perft_divide(0).is_empty());
+    assert_eq!(board.perft(0), 1);
+}
@@ -0,0 +1,166 @@
+use fenex::chess::board::board::Board;
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::board::moves::{Move, MoveKind};
+use fenex::chess::pgn::reader::{parse_pgn, PgnReader};
+use fenex::chess::pgn::{GameResult, PgnGame};
+use fenex::chess::piece::piece::PieceType;
+
+const OPERA_GAME: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 d6 3. d4 Bg4 {This is a weak move already.} 4. dxe5 Bxf3 5. Qxf3
+dxe5 6. Bc4 Nf6 7. Qb3 Qe7 8. Nc3 c6 9. Bg5 b5 10. Nxb5 cxb5 11. Bxb5+ Nbd7
+12. O-O-O Rd8 13. Rxd7 Rxd7 14. Rd1 Qe6 15. Bxd7+ Nxd7 16. Qb8+ Nxb8 17. Rd8# 1-0
+"#;
+
+const CHESS960_GAME: &str = r#"[Variant "Chess960"]
+[SetUp "1"]
+[FEN "k7/8/8/8/8/8/8/5K1R w H - 0 1"]
+
+1. O-O Kb7 2. Rf7+ *
+"#;
+
+#[test]
+fn test_parse_single_game() {
+    let games = parse_pgn(OPERA_GAME).unwrap();
+    assert_eq!(games.len(), 1);
+    let game = &games[0];
+    assert_eq!(game.tag("White"), Some("Paul Morphy"));
+    assert_eq!(game.tag("Black"), Some("Duke Karl / Count Isouard"));
+    assert_eq!(game.result, GameResult::WhiteWins);
+    assert_eq!(game.moves.len(), 33);
+    assert_eq!(
+        game.moves[5].comment.as_deref(),
+        Some("This is a weak move already.")
+    );
+    let board = game.final_board().unwrap();
+    assert!(board.is_checkmate());
+    assert_eq!(
+        board.to_fen(),
+        "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17"
+    );
+}
+
+#[test]
+fn test_variations_nags_and_comments() {
+    let pgn = r#"[Event "Annotated"]
+[Result "*"]
+
+{Opening comment} 1. e4 $1 e5 (1... c5 2. Nf3 (2. c3 d5) d6; rest of line
+(2... Nc6)) 2. Nf3!? Nc6?! (2... d6 {Philidor}) 3. Bb5 *
+"#;
+    let games = parse_pgn(pgn).unwrap();
+    let game = &games[0];
+    assert_eq!(game.result, GameResult::Unknown);
+    assert_eq!(game.moves.len(), 5);
+    assert_eq!(
+        game.moves[0].comment_before.as_deref(),
+        Some("Opening comment")
+    );
+    assert_eq!(game.moves[0].nags, vec![1]);
+    assert_eq!(game.moves[2].nags, vec![5]);
+    assert_eq!(game.moves[3].nags, vec![6]);
+
+    // 1... c5 replaces 1... e5 and contains a nested variation
+    let sicilian = &game.moves[1].variations[0];
+    assert_eq!(sicilian.len(), 3);
+    assert_eq!(sicilian[0].san, "c5");
+    assert_eq!(sicilian[1].variations[0][1].san, "d5");
+    assert_eq!(sicilian[2].comment.as_deref(), Some("rest of line"));
+    assert_eq!(sicilian[2].variations[0][0].san, "Nc6");
+
+    let philidor = &game.moves[3].variations[0];
+    assert_eq!(philidor[0].mv.to, Coordinates::new(4, 6));
+    assert_eq!(philidor[0].comment.as_deref(), Some("Philidor"));
+}
+
+#[test]
+fn test_stream_multiple_games() {
+    let pgn = format!(
+        "{}\n[Event \"Second\"]\n[SetUp \"1\"]\n[FEN \"8/P7/8/8/8/8/8/k6K w - - 0 1\"]\n\n1. a8=N 1/2-1/2\n\n1. d4 d5 0-1\n",
+        OPERA_GAME
+    );
+    let games: Vec<_> = PgnReader::new(pgn.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[1].tag("Event"), Some("Second"));
+    assert_eq!(games[1].moves[0].mv.promotion, Some(PieceType::Knight));
+    assert_eq!(games[1].result, GameResult::Draw);
+    assert!(games[2].tags.is_empty());
+    assert_eq!(games[2].result, GameResult::BlackWins);
+}
+
+#[test]
+fn test_invalid_pgn() {
+    assert!(parse_pgn("1. e4 e5 2. Ke3 *").is_err());
+    assert!(parse_pgn("1. e4 (1. d4 *").is_err());
+    assert!(parse_pgn("1. e4 {unterminated").is_err());
+    assert!(parse_pgn("[Event \"Broken]\n1. e4 *").is_err());
+}
+
+#[test]
+fn test_write_game_from_moves() {
+    let mut board = Board::starting_position();
+    let start = board.clone();
+    let mut moves = Vec::new();
+    for san in ["f3", "e5", "g4", "Qh4"] {
+        let mv = board.parse_san(san).unwrap();
+        board.apply_move(&mv).unwrap();
+        moves.push(mv);
+    }
+    let mut game = PgnGame::from_moves(&start, &moves, GameResult::BlackWins).unwrap();
+    game.set_tag("White", "Fool");
+    game.set_tag("Annotator", "fenex");
+    assert_eq!(
+        game.to_pgn().unwrap(),
+        "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n\
+         [White \"Fool\"]\n[Black \"?\"]\n[Result \"0-1\"]\n[Annotator \"fenex\"]\n\n\
+         1. f3 e5 2. g4 Qh4# 0-1\n"
+    );
+}
+
+#[test]
+fn test_write_setup_annotations_and_wrapping() {
+    let start = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 40").unwrap();
+    let pgn = "[Event \"Endgame\"]\n[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 40\"]\n\n\
+               40... Kd7 {The king heads for the centre} 41. e4 $1 (41. Kd2 Ke6) 41... Ke6 *";
+    let game = &parse_pgn(pgn).unwrap()[0];
+    assert_eq!(game.starting_board().unwrap(), start);
+    let written = game.to_pgn().unwrap();
+    assert!(written.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 40\"]\n"));
+    assert!(written.ends_with(
+        "\n40... Kd7 {The king heads for the centre} 41. e4 $1 (41. Kd2 Ke6) 41... Ke6 *\n"
+    ));
+    // Written output parses back to the same game
+    assert_eq!(&parse_pgn(&written).unwrap()[0].moves, &game.moves);
+
+    let games = parse_pgn(OPERA_GAME).unwrap();
+    let written = games[0].to_pgn().unwrap();
+    assert!(written.lines().all(|line| line.len() <= 80));
+    assert_eq!(parse_pgn(&written).unwrap()[0].moves, games[0].moves);
+}
+
+#[test]
+fn test_chess960_castling() {
+    let game = &parse_pgn(CHESS960_GAME).unwrap()[0];
+    assert_eq!(game.moves.len(), 3);
+    assert_eq!(game.moves[0].mv.kind, MoveKind::KingsideCastle);
+    let board = game.final_board().unwrap();
+    assert_eq!(
+        board.to_fen().split(' ').next(),
+        Some("8/1k3R2/8/8/8/8/8/6K1")
+    );
+    assert!(game.to_pgn().unwrap().ends_with("\n1. O-O Kb7 2. Rf7+ *\n"));
+
+    let moves: Vec<Move> = game.moves.iter().map(|pgn_move| pgn_move.mv).collect();
+    let start = game.starting_board().unwrap();
+    let written = PgnGame::from_moves(&start, &moves, GameResult::Unknown).unwrap();
+    assert_eq!(written.moves[0].san, "O-O");
+    assert_eq!(written.final_board().unwrap(), board);
+}
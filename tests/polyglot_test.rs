@@ -0,0 +1,270 @@
+use std::io::Cursor;
+
+use fenex::chess::board::board::Board;
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::board::moves::{Move, MoveKind};
+use fenex::chess::error::ChessError;
+use fenex::chess::pgn::reader::parse_pgn;
+use fenex::chess::piece::piece::PieceType;
+use fenex::chess::polyglot::reader::PolyglotBook;
+use fenex::chess::polyglot::writer::{BookBuilder, PgnImport};
+use fenex::chess::polyglot::{decode_move, encode_move, polyglot_key, BookEntry};
+
+const CASTLING_FEN: &str = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+fn entry(board: &Board, uci: &str, weight: u16) -> BookEntry {
+    BookEntry {
+        key: polyglot_key(board),
+        mv: encode_move(&board.parse_uci(uci).unwrap()),
+        weight,
+        learn: 0,
+    }
+}
+
+/// Builds a sorted book with filler entries around the positions under test
+fn test_book() -> PolyglotBook<Cursor<Vec<u8>>> {
+    let start = Board::starting_position();
+    let castling = Board::from_fen(CASTLING_FEN).unwrap();
+    let filler = |key| BookEntry {
+        key,
+        mv: 0,
+        weight: 1,
+        learn: 0,
+    };
+    let mut entries = [
+        filler(1),
+        filler(u64::MAX),
+        entry(&start, "e2e4", 10),
+        entry(&start, "d2d4", 30),
+        // e2e5 is not legal and must be skipped
+        BookEntry {
+            key: polyglot_key(&start),
+            mv: 12 << 6 | 36,
+            weight: 100,
+            learn: 0,
+        },
+        entry(&castling, "e1h1", 5),
+        entry(&castling, "e1a1", 2),
+        filler(0x0123_4567_89AB_CDEF),
+    ];
+    entries.sort_by_key(|entry| entry.key);
+    let bytes: Vec<u8> = entries.iter().flat_map(BookEntry::to_bytes).collect();
+    PolyglotBook::new(Cursor::new(bytes)).unwrap()
+}
+
+#[test]
+fn test_polyglot_key() {
+    assert_eq!(
+        polyglot_key(&Board::starting_position()),
+        0x463b96181691fc9c
+    );
+}
+
+#[test]
+fn test_move_encoding() {
+    let board = Board::from_fen(CASTLING_FEN).unwrap();
+    let castle = board.parse_san("O-O").unwrap();
+    assert_eq!(encode_move(&castle), 4 << 6 | 7);
+    assert_eq!(decode_move(&board, 4 << 6 | 7), Some(castle));
+    let long_castle = decode_move(&board, 4 << 6).unwrap();
+    assert_eq!(long_castle.kind, MoveKind::QueensideCastle);
+
+    let board = Board::from_fen("8/P7/8/8/8/8/8/k6K w - - 0 1").unwrap();
+    let promotion = decode_move(&board, 1 << 12 | 48 << 6 | 56).unwrap();
+    assert_eq!(promotion.promotion, Some(PieceType::Knight));
+    assert_eq!(encode_move(&promotion), 1 << 12 | 48 << 6 | 56);
+    assert_eq!(decode_move(&board, 48 << 6 | 56), None);
+
+    let entry = BookEntry {
+        key: 0x0102_0304_0506_0708,
+        mv: 0x090A,
+        weight: 0x0B0C,
+        learn: 0x0D0E_0F10,
+    };
+    assert_eq!(
+        entry.to_bytes(),
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+    );
+    assert_eq!(BookEntry::from_bytes(entry.to_bytes()), entry);
+}
+
+#[test]
+fn test_book_lookup() {
+    let mut book = test_book();
+    assert_eq!(book.len(), 8);
+    let start = Board::starting_position();
+
+    assert_eq!(book.entries(&start).unwrap().len(), 3);
+    let moves: Vec<(String, u16)> = book
+        .moves(&start)
+        .unwrap()
+        .iter()
+        .map(|(mv, weight)| (mv.to_uci(), *weight))
+        .collect();
+    assert_eq!(
+        moves,
+        vec![("e2e4".to_string(), 10), ("d2d4".to_string(), 30)]
+    );
+
+    let castling = Board::from_fen(CASTLING_FEN).unwrap();
+    let moves = book.moves(&castling).unwrap();
+    assert_eq!(moves.len(), 2);
+    assert_eq!(moves[0].0.kind, MoveKind::KingsideCastle);
+    assert_eq!(moves[1].0.kind, MoveKind::QueensideCastle);
+
+    let mut out_of_book = start.clone();
+    out_of_book.apply_uci("a2a3").unwrap();
+    assert!(book.moves(&out_of_book).unwrap().is_empty());
+    assert_eq!(book.best_move(&out_of_book).unwrap(), None);
+    assert_eq!(book.weighted_move(&out_of_book, 7).unwrap(), None);
+}
+
+#[test]
+fn test_book_selection() {
+    let mut book = test_book();
+    let start = Board::starting_position();
+    let uci = |mv: Option<_>| mv.map(|mv: fenex::chess::board::moves::Move| mv.to_uci());
+
+    assert_eq!(uci(book.best_move(&start).unwrap()), Some("d2d4".into()));
+    assert_eq!(
+        uci(book.weighted_move(&start, 0).unwrap()),
+        Some("e2e4".into())
+    );
+    assert_eq!(
+        uci(book.weighted_move(&start, 9).unwrap()),
+        Some("e2e4".into())
+    );
+    assert_eq!(
+        uci(book.weighted_move(&start, 10).unwrap()),
+        Some("d2d4".into())
+    );
+    assert_eq!(
+        uci(book.weighted_move(&start, 39).unwrap()),
+        Some("d2d4".into())
+    );
+    assert_eq!(
+        uci(book.weighted_move(&start, 45).unwrap()),
+        Some("e2e4".into())
+    );
+}
+
+#[test]
+fn test_truncated_book() {
+    assert!(matches!(
+        PolyglotBook::new(Cursor::new(vec![0; 15])),
+        Err(ChessError::InvalidBook(_))
+    ));
+    assert!(PolyglotBook::new(Cursor::new(Vec::new()))
+        .unwrap()
+        .is_empty());
+}
+
+const GAMES: &str = "[Result \"1-0\"]
+
+1. e4 e5 2. Nf3 1-0
+
+[Result \"0-1\"]
+
+1. e4 c5 0-1
+
+[Result \"1/2-1/2\"]
+
+1. d4 d5 1/2-1/2
+";
+
+fn book_moves(builder: &BookBuilder, board: &Board) -> Vec<(String, u16)> {
+    let mut bytes = Vec::new();
+    builder.write(&mut bytes).unwrap();
+    let mut book = PolyglotBook::new(Cursor::new(bytes)).unwrap();
+    book.moves(board)
+        .unwrap()
+        .iter()
+        .map(|(mv, weight)| (mv.to_uci(), *weight))
+        .collect()
+}
+
+#[test]
+fn test_book_builder() {
+    let start = Board::starting_position();
+    let mut after_e4 = start.clone();
+    after_e4.apply_uci("e2e4").unwrap();
+
+    let mut builder = BookBuilder::new();
+    assert_eq!(builder.add_pgn(GAMES.as_bytes()).unwrap().added, 3);
+    assert_eq!(
+        book_moves(&builder, &start),
+        vec![("e2e4".to_string(), 2), ("d2d4".to_string(), 1)]
+    );
+    // 1... e5 only appears in a lost game, so it scores nothing and is left out
+    assert_eq!(
+        book_moves(&builder, &after_e4),
+        vec![("c7c5".to_string(), 2)]
+    );
+
+    let entries = builder.entries();
+    assert!(entries.windows(2).all(|pair| pair[0].key <= pair[1].key));
+
+    let mut frequent = BookBuilder::new().min_games(2);
+    frequent.add_pgn(GAMES.as_bytes()).unwrap();
+    assert_eq!(frequent.entries().len(), 1);
+    assert_eq!(book_moves(&frequent, &start), vec![("e2e4".to_string(), 2)]);
+
+    let mut shallow = BookBuilder::new().max_ply(1).result_weights(1, 1, 1);
+    shallow.add_pgn(GAMES.as_bytes()).unwrap();
+    assert_eq!(shallow.entries().len(), 2);
+    assert!(book_moves(&shallow, &after_e4).is_empty());
+
+    let mut even = BookBuilder::new().result_weights(1, 1, 1);
+    even.add_pgn(GAMES.as_bytes()).unwrap();
+    assert_eq!(
+        book_moves(&even, &after_e4),
+        vec![("c7c5".to_string(), 1), ("e7e5".to_string(), 1)]
+    );
+}
+
+#[test]
+fn test_book_builder_chess960_castling() {
+    let pgn = "[FEN \"k7/8/8/8/8/8/8/5K1R w H - 0 1\"]\n[Result \"1-0\"]\n\n1. O-O Kb7 1-0\n";
+    let mut builder = BookBuilder::new().result_weights(1, 1, 1);
+    assert_eq!(builder.add_pgn(pgn.as_bytes()).unwrap().added, 1);
+
+    let mut board = Board::from_fen("k7/8/8/8/8/8/8/5K1R w H - 0 1").unwrap();
+    let castle = board.parse_san("O-O").unwrap();
+    assert_eq!(castle.kind, MoveKind::KingsideCastle);
+    let entries = builder.entries();
+    assert!(entries
+        .iter()
+        .any(|entry| entry.key == polyglot_key(&board) && entry.mv == encode_move(&castle)));
+    board.apply_move(&castle).unwrap();
+    assert!(entries
+        .iter()
+        .any(|entry| entry.key == polyglot_key(&board)));
+}
+
+#[test]
+fn test_book_builder_skips_bad_games() {
+    let pgn = format!(
+        "{}\n[Result \"1-0\"]\n\n1. e4 e5 2. Ke3 1-0\n\n\
+         [FEN \"not a fen\"]\n[Result \"1-0\"]\n\n1. e4 1-0\n\n[Result \"1-0\"]\n\n1. e4 c5 1-0\n",
+        GAMES
+    );
+    let mut builder = BookBuilder::new();
+    assert_eq!(
+        builder.add_pgn(pgn.as_bytes()).unwrap(),
+        PgnImport {
+            added: 4,
+            skipped: 2
+        }
+    );
+    assert_eq!(
+        book_moves(&builder, &Board::starting_position()),
+        vec![("e2e4".to_string(), 4), ("d2d4".to_string(), 1)]
+    );
+
+    // A game that cannot be replayed adds none of the moves before its bad one
+    let mut game = parse_pgn("1. e4 e5 2. Nf3 1-0").unwrap().remove(0);
+    game.moves[2].mv = Move::new(Coordinates::new(5, 1), Coordinates::new(5, 3));
+    let mut builder = BookBuilder::new();
+    assert!(builder.add_game(&game).is_err());
+    assert!(builder.entries().is_empty());
+}
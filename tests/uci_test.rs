@@ -0,0 +1,370 @@
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::process::Command;
+use std::rc::Rc;
+
+use fenex::chess::board::board::Board;
+use fenex::chess::board::moves::Move;
+use fenex::chess::error::ChessError;
+use fenex::chess::piece::piece::Color;
+use fenex::chess::uci::client::{SearchEvent, UciEngine};
+use fenex::chess::uci::server::{SearchControl, UciPlayer, UciServer};
+use fenex::chess::uci::{GoParams, Info, Score, UciOption, UciOptionKind};
+
+/// Starts the scripted stand-in engine from `tests/engines`
+fn scripted_engine() -> UciEngine {
+    let mut command = Command::new("sh");
+    command.arg(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/engines/scripted_uci_engine.sh"
+    ));
+    UciEngine::from_command(command).unwrap()
+}
+
+#[test]
+fn test_uci_handshake() {
+    let mut engine = scripted_engine();
+    assert_eq!(engine.name(), Some("Scripted Engine 1.0"));
+    assert_eq!(engine.author(), Some("Fenex Tests"));
+    let options = engine.options();
+    assert_eq!(options.len(), 5);
+    assert_eq!(
+        options[0],
+        UciOption {
+            name: "Hash".to_string(),
+            kind: UciOptionKind::Spin {
+                default: 16,
+                min: 1,
+                max: 1024
+            },
+        }
+    );
+    assert_eq!(options[1].kind, UciOptionKind::Check { default: false });
+    assert_eq!(
+        options[2].kind,
+        UciOptionKind::Combo {
+            default: "Normal".to_string(),
+            vars: vec![
+                "Solid".to_string(),
+                "Normal".to_string(),
+                "Very Risky".to_string()
+            ],
+        }
+    );
+    assert_eq!(options[3].name, "Clear Hash");
+    assert_eq!(options[3].kind, UciOptionKind::Button);
+    assert_eq!(options[4].name, "Book File");
+    engine.is_ready().unwrap();
+    engine.new_game().unwrap();
+    engine.quit().unwrap();
+}
+
+#[test]
+fn test_uci_search() {
+    let mut engine = scripted_engine();
+    engine.set_option("Hash", "64").unwrap();
+    let start = Board::starting_position();
+    let e4 = start.parse_uci("e2e4").unwrap();
+    engine.set_position(&start, &[e4]).unwrap();
+    let result = engine.go(&GoParams::depth(2)).unwrap();
+
+    let mut after_e4 = start.clone();
+    after_e4.apply_move(&e4).unwrap();
+    let e5 = after_e4.parse_uci("e7e5").unwrap();
+    let mut after_e5 = after_e4.clone();
+    after_e5.apply_move(&e5).unwrap();
+    let nf3 = after_e5.parse_uci("g1f3").unwrap();
+    assert_eq!(result.best_move.mv, Some(e5));
+    assert_eq!(result.best_move.ponder, Some(nf3));
+
+    let infos = result.infos;
+    assert_eq!(infos.len(), 5);
+    assert_eq!(
+        infos[0].string.as_deref(),
+        Some("position fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 moves e2e4")
+    );
+    assert_eq!(
+        infos[1].string.as_deref(),
+        Some("setoption name Hash value 64")
+    );
+    assert_eq!(
+        infos[2],
+        Info {
+            depth: Some(1),
+            seldepth: Some(2),
+            time: Some(10),
+            nodes: Some(30),
+            nps: Some(3000),
+            multipv: Some(1),
+            score: Some(Score::Centipawns(20)),
+            pv: vec![e5, nf3],
+            string: None,
+        }
+    );
+    assert_eq!(infos[3].multipv, Some(2));
+    assert_eq!(infos[3].score, Some(Score::Centipawns(-5)));
+    assert_eq!(infos[3].pv.len(), 3);
+    // The PV stops at the first move that cannot be played
+    assert_eq!(infos[4].score, Some(Score::Mate(-3)));
+    assert_eq!(infos[4].pv.len(), 3);
+}
+
+#[test]
+fn test_uci_infinite_search_and_stop() {
+    let mut engine = scripted_engine();
+    engine.start_search(&GoParams::infinite()).unwrap();
+    assert!(matches!(engine.read_event().unwrap(), SearchEvent::Info(_)));
+    match engine.read_event().unwrap() {
+        SearchEvent::Info(info) => assert_eq!(info.depth, Some(1)),
+        event => panic!("unexpected {:?}", event),
+    }
+    engine.stop().unwrap();
+    match engine.read_event().unwrap() {
+        SearchEvent::BestMove(best_move) => assert_eq!(best_move.mv, None),
+        event => panic!("unexpected {:?}", event),
+    }
+}
+
+#[test]
+fn test_uci_commands_and_errors() {
+    let params = GoParams {
+        wtime: Some(60000),
+        btime: Some(55000),
+        winc: Some(1000),
+        binc: Some(1000),
+        movestogo: Some(20),
+        ..GoParams::default()
+    };
+    assert_eq!(
+        params.to_command(),
+        "go wtime 60000 btime 55000 winc 1000 binc 1000 movestogo 20"
+    );
+    assert_eq!(GoParams::infinite().to_command(), "go infinite");
+    assert_eq!(GoParams::movetime(500).to_command(), "go movetime 500");
+
+    assert!(UciOption::parse("option name Odd type colour default red").is_none());
+    assert!(matches!(
+        UciEngine::spawn("/nonexistent/fenex-engine"),
+        Err(ChessError::Io(_))
+    ));
+    // An engine that exits during the handshake
+    let mut command = Command::new("sh");
+    command.args(["-c", "read line; echo 'id name Quitter'"]);
+    assert!(matches!(
+        UciEngine::from_command(command),
+        Err(ChessError::Engine(_))
+    ));
+}
+
+/// Plays the first legal move, logging the commands it receives
+struct FirstMovePlayer {
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl UciPlayer for FirstMovePlayer {
+    fn name(&self) -> String {
+        "First Move".to_string()
+    }
+
+    fn author(&self) -> String {
+        "Fenex Tests".to_string()
+    }
+
+    fn options(&self) -> Vec<UciOption> {
+        vec![UciOption {
+            name: "Move Overhead".to_string(),
+            kind: UciOptionKind::Spin {
+                default: 10,
+                min: 0,
+                max: 5000,
+            },
+        }]
+    }
+
+    fn set_option(&mut self, name: &str, value: Option<&str>) {
+        self.log.borrow_mut().push(format!("{}={:?}", name, value));
+    }
+
+    fn new_game(&mut self) {
+        self.log.borrow_mut().push("new game".to_string());
+    }
+
+    fn go(
+        &mut self,
+        board: &Board,
+        params: &GoParams,
+        control: &mut SearchControl<'_>,
+    ) -> Option<Move> {
+        self.log.borrow_mut().push(format!("{:?}", params));
+        if params.infinite {
+            control.wait_for_stop();
+        }
+        let mv = board.generate_moves().into_iter().next();
+        control.report(&Info {
+            depth: Some(1),
+            score: Some(Score::Centipawns(0)),
+            pv: mv.into_iter().collect(),
+            ..Info::default()
+        });
+        mv
+    }
+}
+
+/// Runs a server over the given commands, returning its output lines and the player's log
+fn serve(commands: &str) -> (Vec<String>, Vec<String>) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let player = FirstMovePlayer {
+        log: Rc::clone(&log),
+    };
+    let mut server = UciServer::new(Box::new(player));
+    let mut output = Vec::new();
+    server
+        .run(Cursor::new(commands.as_bytes().to_vec()), &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let log = log.borrow().clone();
+    (output.lines().map(str::to_string).collect(), log)
+}
+
+#[test]
+fn test_uci_server_answers_isready_while_searching() {
+    let (input, mut commands) = std::io::pipe().unwrap();
+    let (replies, output) = std::io::pipe().unwrap();
+    // Drives the GUI side: the infinite search only ends after `readyok` arrives
+    let gui = std::thread::spawn(move || {
+        let mut replies = BufReader::new(replies).lines().map(Result::unwrap);
+        writeln!(commands, "go infinite").unwrap();
+        writeln!(commands, "isready").unwrap();
+        let before_stop: Vec<String> = replies.by_ref().take(1).collect();
+        writeln!(commands, "stop").unwrap();
+        let mut after_stop: Vec<String> = replies.by_ref().take(2).collect();
+        writeln!(commands, "isready").unwrap();
+        writeln!(commands, "quit").unwrap();
+        after_stop.extend(replies);
+        (before_stop, after_stop)
+    });
+
+    let player = FirstMovePlayer {
+        log: Rc::new(RefCell::new(Vec::new())),
+    };
+    UciServer::new(Box::new(player))
+        .run(BufReader::new(input), output)
+        .unwrap();
+    let (before_stop, after_stop) = gui.join().unwrap();
+    assert_eq!(before_stop, vec!["readyok".to_string()]);
+    let first = Board::starting_position().generate_moves()[0].to_uci();
+    assert_eq!(
+        after_stop,
+        vec![
+            format!("info depth 1 score cp 0 pv {}", first),
+            format!("bestmove {}", first),
+            "readyok".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_uci_server_session() {
+    let (output, log) = serve(
+        "uci\n\
+         setoption name Move Overhead value 30\n\
+         setoption name Clear Hash\n\
+         isready\n\
+         ucinewgame\n\
+         position startpos moves e2e4 e7e5\n\
+         go wtime 60000 btime 50000 winc 1000 binc 500 movestogo 20\n\
+         position fen 4k3/8/8/8/8/8/8/4K2R w K - 0 1 moves e1g1\n\
+         go depth 3 nodes 5000 movetime 200\n\
+         position startpos moves e2e5\n\
+         go infinite\n\
+         stop\n\
+         quit\n\
+         go depth 1\n",
+    );
+
+    let mut board = Board::starting_position();
+    board.apply_uci("e2e4").unwrap();
+    board.apply_uci("e7e5").unwrap();
+    let first = board.generate_moves()[0].to_uci();
+    let mut castled = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    castled.apply_uci("e1g1").unwrap();
+    let castled_first = castled.generate_moves()[0].to_uci();
+    // The illegal e2e5 leaves the castled position in place
+    assert_eq!(
+        output,
+        vec![
+            "id name First Move".to_string(),
+            "id author Fenex Tests".to_string(),
+            "option name Move Overhead type spin default 10 min 0 max 5000".to_string(),
+            "uciok".to_string(),
+            "readyok".to_string(),
+            format!("info depth 1 score cp 0 pv {}", first),
+            format!("bestmove {}", first),
+            format!("info depth 1 score cp 0 pv {}", castled_first),
+            format!("bestmove {}", castled_first),
+            "info string Illegal move: e2e5".to_string(),
+            format!("info depth 1 score cp 0 pv {}", castled_first),
+            format!("bestmove {}", castled_first),
+        ]
+    );
+
+    let clock = GoParams {
+        wtime: Some(60000),
+        btime: Some(50000),
+        winc: Some(1000),
+        binc: Some(500),
+        movestogo: Some(20),
+        ..GoParams::default()
+    };
+    let fixed = GoParams {
+        depth: Some(3),
+        nodes: Some(5000),
+        movetime: Some(200),
+        ..GoParams::default()
+    };
+    assert_eq!(
+        log,
+        vec![
+            "Move Overhead=Some(\"30\")".to_string(),
+            "Clear Hash=None".to_string(),
+            "new game".to_string(),
+            format!("{:?}", clock),
+            format!("{:?}", fixed),
+            format!("{:?}", GoParams::infinite()),
+        ]
+    );
+}
+
+#[test]
+fn test_go_params_and_info_lines() {
+    let params = GoParams::parse("go wtime 60000 btime 50000 winc 1000 binc 500 movestogo 20");
+    assert_eq!(GoParams::parse(&params.to_command()), params);
+    assert_eq!(params.time_budget(Color::White), Some(3750));
+    assert_eq!(params.time_budget(Color::Black), Some(2875));
+    assert_eq!(GoParams::movetime(200).time_budget(Color::White), Some(200));
+    assert_eq!(GoParams::infinite().time_budget(Color::White), None);
+    assert_eq!(GoParams::depth(5).time_budget(Color::Black), None);
+    // Never more than half the clock
+    assert_eq!(
+        GoParams::parse("wtime 100 winc 1000").time_budget(Color::White),
+        Some(50)
+    );
+
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    let castle = board.parse_uci("e1g1").unwrap();
+    let info = Info {
+        depth: Some(4),
+        seldepth: Some(6),
+        multipv: Some(1),
+        score: Some(Score::Mate(2)),
+        nodes: Some(1000),
+        nps: Some(50000),
+        time: Some(20),
+        pv: vec![castle],
+        string: Some("done".to_string()),
+    };
+    let line = "info depth 4 seldepth 6 multipv 1 score mate 2 nodes 1000 nps 50000 time 20 pv e1g1 string done";
+    assert_eq!(info.to_command(&board, false), line);
+    assert_eq!(Info::parse(line, &board), info);
+    assert!(info.to_command(&board, true).contains("pv e1h1 "));
+}
@@ -0,0 +1,338 @@
+use std::cell::RefCell;
+use std::io::Cursor;
+use std::process::Command;
+use std::rc::Rc;
+
+use fenex::chess::board::board::Board;
+use fenex::chess::board::moves::Move;
+use fenex::chess::error::ChessError;
+use fenex::chess::pgn::GameResult;
+use fenex::chess::uci::server::{SearchControl, UciPlayer};
+use fenex::chess::uci::{GoParams, Info, Score, UciOption, UciOptionKind};
+use fenex::chess::xboard::controller::{EngineAction, XboardEngine};
+use fenex::chess::xboard::engine::XboardServer;
+use fenex::chess::xboard::{
+    format_move, format_thinking, option_feature, parse_move, parse_option_feature, parse_thinking,
+    Feature, MoveFormat, TimeControl,
+};
+
+fn scripted_engine() -> XboardEngine {
+    let mut command = Command::new("sh");
+    command.arg(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/engines/scripted_xboard_engine.sh"
+    ));
+    XboardEngine::from_command(command).unwrap()
+}
+
+#[test]
+fn test_xboard_notation() {
+    let level = TimeControl::parse("level 40 5 0").unwrap();
+    assert_eq!(
+        level,
+        TimeControl {
+            moves: 40,
+            base: 300000,
+            increment: 0
+        }
+    );
+    assert_eq!(level.to_command(), "level 40 5 0");
+    let level = TimeControl::parse("0 2:30 1.5").unwrap();
+    assert_eq!((level.base, level.increment), (150000, 1500));
+    assert_eq!(level.to_command(), "level 0 2:30 1.5");
+    assert_eq!(TimeControl::parse("level 40 five 0"), None);
+
+    let features = Feature::parse_line(r#"feature myname="Fenex Test 1.0" san=1 done=0"#);
+    assert_eq!(
+        features,
+        vec![
+            Feature::new("myname", "Fenex Test 1.0"),
+            Feature::new("san", "1"),
+            Feature::new("done", "0"),
+        ]
+    );
+    assert_eq!(features[0].to_command(), r#"myname="Fenex Test 1.0""#);
+    assert_eq!(features[1].to_command(), "san=1");
+
+    let style = UciOption {
+        name: "Style".to_string(),
+        kind: UciOptionKind::Combo {
+            default: "Normal".to_string(),
+            vars: vec!["Solid".to_string(), "Normal".to_string()],
+        },
+    };
+    assert_eq!(option_feature(&style), "Style -combo Solid /// *Normal");
+    assert_eq!(parse_option_feature(&option_feature(&style)), Some(style));
+    assert_eq!(
+        parse_option_feature("Skill -slider 5 0 20").map(|option| option.kind),
+        Some(UciOptionKind::Spin {
+            default: 5,
+            min: 0,
+            max: 20
+        })
+    );
+    assert_eq!(parse_option_feature("Skill -knob 5"), None);
+
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    let castle = parse_move(&board, "O-O").unwrap();
+    assert_eq!(parse_move(&board, "e1g1"), Ok(castle));
+    assert_eq!(parse_move(&board, "e1h1"), Ok(castle));
+    assert_eq!(
+        format_move(&board, &castle, MoveFormat::Coordinate, false).unwrap(),
+        "e1g1"
+    );
+    assert_eq!(
+        format_move(&board, &castle, MoveFormat::Coordinate, true).unwrap(),
+        "O-O"
+    );
+    assert_eq!(
+        format_move(&board, &castle, MoveFormat::San, false).unwrap(),
+        "O-O"
+    );
+    assert_eq!(
+        parse_move(&board, "Rh8+").map(|mv| mv.to_uci()),
+        Ok("h1h8".to_string())
+    );
+    assert!(matches!(
+        parse_move(&board, "e1e3"),
+        Err(ChessError::IllegalMove(_))
+    ));
+
+    let start = Board::starting_position();
+    let info = parse_thinking("9 -100003 152 40000 1. e4 e5 2. Nf3 zz Nc6", &start).unwrap();
+    assert_eq!(info.depth, Some(9));
+    assert_eq!(info.score, Some(Score::Mate(-3)));
+    assert_eq!(info.time, Some(1520));
+    assert_eq!(info.nodes, Some(40000));
+    assert_eq!(info.pv.len(), 3);
+    assert_eq!(
+        format_thinking(&info, &start),
+        "9 -100003 152 40000 e4 e5 Nf3"
+    );
+    assert_eq!(parse_thinking("move e2e4", &start), None);
+}
+
+/// Plays the first legal move, logging the search limits it receives
+struct FirstMovePlayer {
+    log: Rc<RefCell<Vec<GoParams>>>,
+}
+
+impl UciPlayer for FirstMovePlayer {
+    fn name(&self) -> String {
+        "First Move".to_string()
+    }
+
+    fn options(&self) -> Vec<UciOption> {
+        vec![UciOption {
+            name: "Move Overhead".to_string(),
+            kind: UciOptionKind::Spin {
+                default: 10,
+                min: 0,
+                max: 5000,
+            },
+        }]
+    }
+
+    fn go(
+        &mut self,
+        board: &Board,
+        params: &GoParams,
+        control: &mut SearchControl<'_>,
+    ) -> Option<Move> {
+        self.log.borrow_mut().push(params.clone());
+        let mv = board.generate_moves().into_iter().next();
+        control.report(&Info {
+            depth: Some(1),
+            score: Some(Score::Centipawns(12)),
+            time: Some(30),
+            nodes: Some(20),
+            pv: mv.into_iter().collect(),
+            ..Info::default()
+        });
+        mv
+    }
+}
+
+/// Runs a server over the given commands, returning it with its output lines and the player's log
+fn serve(commands: &str) -> (XboardServer, Vec<String>, Vec<GoParams>) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let player = FirstMovePlayer {
+        log: Rc::clone(&log),
+    };
+    let mut server = XboardServer::new(Box::new(player));
+    let mut output = Vec::new();
+    server
+        .run(Cursor::new(commands.as_bytes().to_vec()), &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let log = log.borrow().clone();
+    (server, output.lines().map(str::to_string).collect(), log)
+}
+
+#[test]
+fn test_xboard_server_game() {
+    let (server, output, log) = serve(
+        "xboard\n\
+         protover 2\n\
+         accepted usermove\n\
+         new\n\
+         post\n\
+         level 40 5 0\n\
+         time 30000\n\
+         otim 29000\n\
+         usermove e2e4\n\
+         ping 1\n\
+         force\n\
+         usermove Nf3\n\
+         undo\n\
+         bogus\n\
+         usermove e1e3\n\
+         quit\n",
+    );
+
+    let mut board = Board::starting_position();
+    board.apply_uci("e2e4").unwrap();
+    let reply = board.generate_moves()[0];
+    let san = board.to_san(&reply).unwrap();
+    board.apply_move(&reply).unwrap();
+    assert_eq!(
+        output,
+        vec![
+            "feature myname=\"First Move\" ping=1 setboard=1 usermove=1 san=0 colors=0 sigint=0 \
+             sigterm=0 reuse=1 analyze=0 variants=\"normal,fischerandom\""
+                .to_string(),
+            "feature option=\"Move Overhead -spin 10 0 5000\"".to_string(),
+            "feature done=1".to_string(),
+            format!("1 12 3 20 {}", san),
+            format!("move {}", reply.to_uci()),
+            "pong 1".to_string(),
+            "Error (unknown command): bogus".to_string(),
+            "Illegal move: e1e3".to_string(),
+        ]
+    );
+    // The engine's clock is Black's, and the undo took back Nf3
+    assert_eq!(
+        log,
+        vec![GoParams {
+            wtime: Some(290000),
+            btime: Some(300000),
+            winc: Some(0),
+            binc: Some(0),
+            movestogo: Some(40),
+            ..GoParams::default()
+        }]
+    );
+    assert_eq!(server.board().to_fen(), board.to_fen());
+}
+
+#[test]
+fn test_xboard_server_setboard_and_result() {
+    let (_, output, log) = serve(
+        "new\n\
+         variant fischerandom\n\
+         setboard 4k3/8/8/8/8/8/8/4K2R w K - 0 1\n\
+         sd 3\n\
+         st 2\n\
+         go\n\
+         setboard not a fen\n\
+         setboard 7k/6Q1/6K1/8/8/8/8/8 b - - 0 1\n\
+         go\n",
+    );
+
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    let first = board.generate_moves()[0];
+    assert_eq!(
+        output,
+        vec![
+            format!(
+                "move {}",
+                format_move(&board, &first, MoveFormat::Coordinate, true).unwrap()
+            ),
+            "Error (illegal position): setboard not a fen".to_string(),
+            "1-0 {White mates}".to_string(),
+        ]
+    );
+    assert_eq!(
+        log,
+        vec![GoParams {
+            depth: Some(3),
+            movetime: Some(2000),
+            ..GoParams::default()
+        }]
+    );
+}
+
+#[test]
+fn test_xboard_controller() {
+    let mut engine = scripted_engine();
+    assert_eq!(engine.name(), Some("Scripted Xboard 1.0"));
+    assert_eq!(engine.move_format(), MoveFormat::San);
+    assert_eq!(engine.feature("setboard"), Some("1"));
+    assert_eq!(engine.feature("colors"), None);
+    let options = engine.options();
+    assert_eq!(options.len(), 3);
+    assert_eq!(
+        options[1].kind,
+        UciOptionKind::Combo {
+            default: "Normal".to_string(),
+            vars: vec![
+                "Solid".to_string(),
+                "Normal".to_string(),
+                "Very Risky".to_string()
+            ],
+        }
+    );
+    assert_eq!(options[2].kind, UciOptionKind::Button);
+
+    let mut board = Board::starting_position();
+    engine.new_game(&board, false).unwrap();
+    engine
+        .set_level(&TimeControl::parse("40 5 0").unwrap())
+        .unwrap();
+    engine.set_clocks(300000, 300000).unwrap();
+    let e4 = board.apply_uci("e2e4").unwrap();
+    engine.send_move(&e4).unwrap();
+    let reply = engine.go().unwrap();
+    let e5 = board.apply_uci("e7e5").unwrap();
+    assert_eq!(reply.action, EngineAction::Move(e5));
+    assert_eq!(reply.thinking.len(), 2);
+    assert_eq!(reply.thinking[0].score, Some(Score::Centipawns(20)));
+    assert_eq!(reply.thinking[0].time, Some(50));
+    assert_eq!(reply.thinking[0].pv, vec![e5]);
+    assert_eq!(reply.thinking[1].score, Some(Score::Mate(-3)));
+    assert_eq!(reply.thinking[1].pv.len(), 3);
+
+    engine.set_option("Hash", Some("64")).unwrap();
+    let nf3 = board.apply_uci("g1f3").unwrap();
+    engine.send_move(&nf3).unwrap();
+    let reply = engine.read_reply().unwrap();
+    let nc6 = board.apply_uci("b8c6").unwrap();
+    assert_eq!(reply.action, EngineAction::Move(nc6));
+    assert_eq!(reply.thinking[0].pv, vec![nc6]);
+
+    let bb5 = board.apply_uci("f1b5").unwrap();
+    engine.send_move(&bb5).unwrap();
+    assert_eq!(engine.read_reply().unwrap().action, EngineAction::Resign);
+    engine
+        .result(GameResult::WhiteWins, "Black resigns")
+        .unwrap();
+    engine.ping().unwrap();
+
+    let fen = "4k3/8/8/8/8/8/8/4K2R w K - 0 1";
+    let board = Board::from_fen(fen).unwrap();
+    engine.new_game(&board, false).unwrap();
+    assert_eq!(
+        engine.go().unwrap().action,
+        EngineAction::Claim {
+            result: GameResult::Draw,
+            comment: format!("setboard {}", fen),
+        }
+    );
+    let ke2 = board.parse_uci("e1e2").unwrap();
+    engine.send_move(&ke2).unwrap();
+    assert!(matches!(
+        engine.read_reply(),
+        Err(ChessError::Engine(line)) if line == "Illegal move: Ke2"
+    ));
+    engine.quit().unwrap();
+}
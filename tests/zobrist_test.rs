@@ -0,0 +1,119 @@
+use fenex::chess::board::board::Board;
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::piece::piece::Color;
+
+/// Checks the incremental hash against a full recomputation at every node of the tree
+fn assert_hashes(board: &mut Board, depth: u32) {
+    assert_eq!(
+        board.zobrist_hash(),
+        board.compute_zobrist_hash(),
+        "{}",
+        board.to_fen()
+    );
+    if depth == 0 {
+        return;
+    }
+    let before = board.zobrist_hash();
+    for mv in board.generate_moves() {
+        let undo = board.make_move(&mv).unwrap();
+        assert_hashes(board, depth - 1);
+        board.unmake_move(undo);
+        assert_eq!(board.zobrist_hash(), before);
+    }
+}
+
+#[test]
+fn test_polyglot_reference_keys() {
+    // Reference keys from the Polyglot book format specification
+    let cases: [(&[&str], u64); 9] = [
+        (&[], 0x463b96181691fc9c),
+        (&["e2e4"], 0x823c9b50fd114196),
+        (&["e2e4", "d7d5"], 0x0756b94461c50fb0),
+        (&["e2e4", "d7d5", "e4e5"], 0x662fafb965db29d4),
+        (&["e2e4", "d7d5", "e4e5", "f7f5"], 0x22a48b5a8e47ff78),
+        (
+            &["e2e4", "d7d5", "e4e5", "f7f5", "e1e2"],
+            0x652a607ca3f242c1,
+        ),
+        (
+            &["e2e4", "d7d5", "e4e5", "f7f5", "e1e2", "e8f7"],
+            0x00fdd303c946bdd9,
+        ),
+        (
+            &["a2a4", "b7b5", "h2h4", "b5b4", "c2c4"],
+            0x3c8123ea7b067637,
+        ),
+        (
+            &["a2a4", "b7b5", "h2h4", "b5b4", "c2c4", "b4c3", "a1a3"],
+            0x5c3f9b829b279560,
+        ),
+    ];
+    for (moves, key) in cases {
+        let mut board = Board::starting_position();
+        for uci in moves {
+            board.apply_uci(uci).unwrap();
+        }
+        assert_eq!(board.zobrist_hash(), key, "{:?}", moves);
+        assert_eq!(board.compute_zobrist_hash(), key, "{:?}", moves);
+        assert_eq!(
+            Board::from_fen(&board.to_fen()).unwrap().zobrist_hash(),
+            key,
+            "{:?}",
+            moves
+        );
+    }
+}
+
+#[test]
+fn test_incremental_hash_matches_recomputation() {
+    for fen in [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ] {
+        let mut board = Board::from_fen(fen).unwrap();
+        assert_hashes(&mut board, 3);
+    }
+}
+
+#[test]
+fn test_hash_distinguishes_positions() {
+    let start = Board::starting_position();
+    let mut transposed = Board::starting_position();
+    for uci in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+        transposed.apply_uci(uci).unwrap();
+    }
+    assert_eq!(start.zobrist_hash(), transposed.zobrist_hash());
+
+    let black_to_move =
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    let no_castling =
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1").unwrap();
+    assert_ne!(start.zobrist_hash(), black_to_move.zobrist_hash());
+    assert_ne!(start.zobrist_hash(), no_castling.zobrist_hash());
+}
+
+#[test]
+fn test_setters_keep_hash_in_sync() {
+    let mut board = Board::starting_position();
+    board.apply_uci("e2e4").unwrap();
+    board.apply_uci("a7a6").unwrap();
+    board.apply_uci("e4e5").unwrap();
+    board.apply_uci("d7d5").unwrap();
+    let expected =
+        Board::from_fen("rnbqkbnr/1pp1pppp/p7/3pP3/8/8/PPPP1PPP/RNBQKBNR b Kq - 0 3").unwrap();
+
+    board.set_color_to_move(Color::Black);
+    board.set_castling_rights([Some(8), None, None, Some(1)]);
+    board.set_en_passant(None);
+    assert_eq!(board.color_to_move(), Color::Black);
+    assert_eq!(board.zobrist_hash(), board.compute_zobrist_hash());
+    assert_eq!(board.zobrist_hash(), expected.zobrist_hash());
+    assert_eq!(board.generate_moves(), expected.generate_moves());
+
+    board.set_color_to_move(Color::White);
+    board.set_en_passant(Some(Coordinates::new(4, 6)));
+    assert_eq!(board.zobrist_hash(), board.compute_zobrist_hash());
+    assert!(board.parse_uci("e5d6").is_ok());
+}